@@ -1,7 +1,83 @@
 use serde::{Deserialize, Serialize};
 
+/// A VCS branch linked to a story by Shortcut's integration.
 #[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Debug)]
 pub struct Branch {
-    id: i32,
-    name: String,
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub pull_requests: Vec<PullRequest>,
+}
+
+impl Branch {
+    /// Shortcut doesn't say whether a branch was merged, only whether its pull requests were.
+    pub fn merged(&self) -> bool {
+        self.pull_requests.iter().any(|pr| pr.merged)
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct PullRequest {
+    pub id: i32,
+    pub number: i32,
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub branch_name: Option<String>,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub merged: bool,
+    #[serde(default)]
+    pub draft: bool,
+    /// e.g. `approved` or `changes_requested`, when the VCS reports it
+    #[serde(default)]
+    pub review_status: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrStatus {
+    Open,
+    Draft,
+    Merged,
+    Closed,
+}
+
+impl PrStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            PrStatus::Open => "open",
+            PrStatus::Draft => "draft",
+            PrStatus::Merged => "merged",
+            PrStatus::Closed => "closed",
+        }
+    }
+}
+
+impl PullRequest {
+    pub fn status(&self) -> PrStatus {
+        if self.merged {
+            PrStatus::Merged
+        } else if self.closed {
+            PrStatus::Closed
+        } else if self.draft {
+            PrStatus::Draft
+        } else {
+            PrStatus::Open
+        }
+    }
+
+    /// Still waiting to be merged, drafts included.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status(), PrStatus::Open | PrStatus::Draft)
+    }
+
+    pub fn review_label(&self) -> Option<String> {
+        self.review_status
+            .as_deref()
+            .filter(|status| !status.is_empty())
+            .map(|status| status.replace('_', " "))
+    }
 }
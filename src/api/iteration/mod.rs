@@ -102,19 +102,32 @@ impl ApiClient {
     async fn get_owned_single_iteration_stories(
         &self,
         iteration_id: i32,
+    ) -> anyhow::Result<Vec<Story>> {
+        self.get_single_iteration_stories(iteration_id, true).await
+    }
+
+    /// Every story in an iteration, regardless of owner.
+    pub async fn get_iteration_stories(&self, iteration_id: i32) -> anyhow::Result<Vec<Story>> {
+        self.get_single_iteration_stories(iteration_id, false).await
+    }
+
+    async fn get_single_iteration_stories(
+        &self,
+        iteration_id: i32,
+        owned_only: bool,
     ) -> anyhow::Result<Vec<Story>> {
         let response = self
             .get(&format!("iterations/{}/stories", iteration_id))
             .await?;
         let stories_slim = response.json::<Vec<StorySlim>>().await?;
-        let slim_owned: Vec<_> = stories_slim
+        let slim_matching: Vec<_> = stories_slim
             .iter()
-            .filter(|s| s.owner_ids.contains(&self.user_id))
+            .filter(|s| !owned_only || s.owner_ids.contains(&self.user_id))
             .collect();
 
         let stories = {
-            let len = slim_owned.len();
-            let futures = slim_owned.into_iter().take(len).map(|slim| async move {
+            let len = slim_matching.len();
+            let futures = slim_matching.into_iter().take(len).map(|slim| async move {
                 let query = format!("stories/{}", slim.id);
                 let response = self.get(&query).await?;
                 response
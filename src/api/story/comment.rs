@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -8,6 +9,15 @@ pub struct StoryComment {
     // numerical position of comment oldest -> newest
     position: i32,
     text: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
     // TODO: show replies in threads/nested
     // parent_id: Option<i32>
 }
+
+impl StoryComment {
+    /// When the comment was posted, if it isn't deleted.
+    pub fn posted_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at.filter(|_| !self.deleted)
+    }
+}
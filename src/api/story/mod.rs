@@ -1,9 +1,18 @@
 use anyhow::Context;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use slugify::slugify;
 use uuid::Uuid;
 
-use crate::api::{ApiClient, branch::Branch, iteration::Iteration, story::comment::StoryComment};
+use crate::{
+    api::{
+        ApiClient,
+        branch::{Branch, PullRequest},
+        iteration::Iteration,
+        story::comment::StoryComment,
+    },
+    multiplexer::SESSION_PREFIX,
+};
 
 pub mod comment;
 
@@ -18,6 +27,31 @@ pub struct Story {
     pub iteration_id: Option<i32>,
     pub name: String,
     pub app_url: String,
+    #[serde(default)]
+    pub started: bool,
+    #[serde(default)]
+    pub labels: Vec<StoryLabel>,
+    #[serde(default)]
+    pub estimate: Option<i32>,
+    #[serde(default)]
+    pub blocked: bool,
+    /// When the story last changed workflow state
+    #[serde(default)]
+    pub moved_at: Option<DateTime<Utc>>,
+    /// `feature`, `bug` or `chore`
+    #[serde(default = "default_story_type")]
+    pub story_type: String,
+    #[serde(default)]
+    pub pull_requests: Vec<PullRequest>,
+}
+
+fn default_story_type() -> String {
+    "feature".to_string()
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct StoryLabel {
+    pub name: String,
 }
 
 #[derive(Deserialize)]
@@ -44,10 +78,7 @@ impl ApiClient {
         let stories = {
             let mut stories = Vec::with_capacity(stories_slim.len());
             for slim in stories_slim.into_iter() {
-                let query = format!("stories/{}", slim.id);
-                let response = self.get(&query).await?;
-                let story = response.json::<Story>().await?;
-                stories.push(story);
+                stories.push(self.get_story(slim.id).await?);
             }
             stories
         };
@@ -55,6 +86,14 @@ impl ApiClient {
         Ok(stories)
     }
 
+    pub async fn get_story(&self, story_id: i32) -> anyhow::Result<Story> {
+        let response = self.get(&format!("stories/{}", story_id)).await?;
+        response
+            .json::<Story>()
+            .await
+            .with_context(|| format!("Failed to fetch story {} from API", story_id))
+    }
+
     pub async fn update_story_description(
         &self,
         story_id: i32,
@@ -77,7 +116,37 @@ impl ApiClient {
 impl Story {
     pub fn tmux_session_name(name: &str) -> String {
         let story_slug = slugify!(name);
-        format!("scn--{}", story_slug)
+        format!("{}{}", SESSION_PREFIX, story_slug)
+    }
+
+    /// Pull requests of the story and its branches, each once.
+    pub fn linked_pull_requests(&self) -> Vec<&PullRequest> {
+        let mut prs: Vec<&PullRequest> = Vec::new();
+        let linked = self
+            .pull_requests
+            .iter()
+            .chain(self.branches.iter().flat_map(|b| &b.pull_requests));
+        for pr in linked {
+            if !prs.iter().any(|seen| seen.id == pr.id) {
+                prs.push(pr);
+            }
+        }
+        prs
+    }
+
+    /// The branch work on the story happens on: the head of its first open pull request,
+    /// otherwise the first branch that is still around.
+    pub fn linked_branch(&self) -> Option<&str> {
+        self.linked_pull_requests()
+            .into_iter()
+            .filter(|pr| pr.is_open())
+            .find_map(|pr| pr.branch_name.as_deref())
+            .or_else(|| {
+                self.branches
+                    .iter()
+                    .find(|b| !b.merged() && !b.deleted)
+                    .map(|b| b.name.as_str())
+            })
     }
 
     pub fn get_file_name(&self) -> String {
@@ -92,3 +161,25 @@ pub fn get_story_associated_iteration<'a>(
     let iteration_id = iteration_id?;
     iterations.into_iter().find(|it| it.id == iteration_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::dummy;
+
+    #[test]
+    fn linked_branch_skips_branches_with_merged_pull_requests() {
+        let mut story = dummy::stories().remove(0);
+        let mut branch = story.branches[0].clone();
+        story.branches[0].name = "jane/sc-101/first-try".to_string();
+        story.branches[0].pull_requests[0].merged = true;
+        branch.pull_requests[0].id = 2;
+        branch.pull_requests[0].closed = true;
+        story.branches.push(branch);
+
+        assert!(story.branches[0].merged());
+        assert_eq!(
+            story.linked_branch(),
+            Some("jane/sc-101/implement-user-authentication")
+        );
+    }
+}
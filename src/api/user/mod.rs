@@ -7,10 +7,17 @@ use crate::api::get_full_path;
 
 #[derive(Deserialize)]
 pub struct Member {
-    id: Uuid,
+    pub id: Uuid,
+    /// The @name used in mentions and branch names
+    pub mention_name: String,
 }
 
 pub async fn get_user_id_from_api(api_token: &str) -> anyhow::Result<Uuid> {
+    Ok(get_member_from_api(api_token).await?.id)
+}
+
+/// The member the API token belongs to.
+pub async fn get_member_from_api(api_token: &str) -> anyhow::Result<Member> {
     let full_path = get_full_path("member");
     // cant do with api client as it isnt instantiated at the point of calling this
     let response = Client::new()
@@ -21,7 +28,7 @@ pub async fn get_user_id_from_api(api_token: &str) -> anyhow::Result<Uuid> {
         .await
         .with_context(|| format!("Failed to GET {}", &full_path))?;
 
-    let user = response.json::<Member>().await?;
+    let member = response.json::<Member>().await?;
 
-    Ok(user.id)
+    Ok(member)
 }
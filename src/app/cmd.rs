@@ -7,18 +7,38 @@ use std::{
 };
 
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use slugify::slugify;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::app::model::Model;
+use crate::app::pane::{notes_list, repo_picker};
 use crate::error::ErrorInfo;
-use crate::tmux::{session_attach, session_create, session_exists};
+use crate::timer::TimeLog;
+use crate::branch_name::branch_name;
+use crate::multiplexer::{self, open_story_session};
+use crate::worktree::{
+    STORY_STATUS_REFRESH, WorktreeInfo, cached_repos, create_worktree, list_worktrees,
+    refresh_repo_cache, remove_worktree, story_git_statuses,
+};
 use crate::{
-    api::{ApiClient, story::Story},
+    api::{ApiClient, iteration::Iteration, story::Story},
     app::msg::Msg,
+    clipboard,
     config::Config,
-    dbg_file,
-    note::Note,
+    dbg_file, dummy,
+    note::{
+        Note,
+        capture::{CaptureTarget, capture},
+        daily::{DAILY_DIR, DailyLink, build_daily_note, daily_note_date},
+        frontmatter::{SCHEMA_VERSION, yaml_string},
+        manage::{TRASH_DIR, UNDO_WINDOW, archive_note, move_note, rename_note, trash_note},
+        report::write_iteration_report,
+        search::SearchIndex,
+        sync::{SyncStatus, commit_and_push, commit_message, pull},
+        tags::{merge_note_tags, set_note_tags},
+        todo::{scan_todos, toggle_todo},
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -40,12 +60,43 @@ pub enum Cmd {
     },
     FetchEpics,
     SelectStory(Option<Story>),
+    /// Starts timing the active story, or stops the running timer
+    ToggleTimer,
     ActionMenuVisibility(bool),
+    /// Creates a worktree for the story in its remembered repo. Without one, or with
+    /// `choose_repo`, a repo is picked with the built in picker unless `use_fzf` is set.
+    /// The branch is named from the template unless `branch_name` is given
     CreateGitWorktree {
+        story: Story,
+        choose_repo: bool,
+        branch_name: Option<String>,
+    },
+    LoadSessions,
+    KillSessions {
+        names: Vec<String>,
+    },
+    /// Suspends the TUI while attached, unless we're already inside a session
+    SwitchToSession {
+        name: String,
+    },
+    LoadWorktrees,
+    RemoveWorktrees {
+        worktrees: Vec<WorktreeInfo>,
+        delete_branches: bool,
+    },
+    /// Creates a worktree in `repo`, relative to `repositories_directory`, and remembers
+    /// the repo for the story. A `linked_branch` has to be on origin
+    CreateWorktreeInRepo {
+        repo: String,
         branch_name: String,
+        linked_branch: bool,
+        story_id: i32,
+        epic_id: Option<i32>,
     },
-    OpenTmuxSession {
-        story_name: String,
+    /// Starts in the story's worktree, or its repo, when one is remembered. Suspends the
+    /// TUI like `SwitchToSession`
+    OpenSession {
+        story: Story,
     },
     Batch(Vec<Cmd>),
     OpenInBrowser {
@@ -68,6 +119,51 @@ pub enum Cmd {
         path: PathBuf,
         name: String,
     },
+    OpenNoteAtLine {
+        path: PathBuf,
+        line: usize,
+    },
+    RenameNote {
+        path: PathBuf,
+        name: String,
+    },
+    MoveNote {
+        path: PathBuf,
+        category: String,
+    },
+    ArchiveNotes {
+        paths: Vec<PathBuf>,
+    },
+    DeleteNote {
+        path: PathBuf,
+    },
+    UndoDeleteNote,
+    SetNoteTags {
+        path: PathBuf,
+        tags: Vec<String>,
+    },
+    /// Adds story labels to the tags of story notes, as `(note, missing tags)`
+    MirrorStoryLabels {
+        notes: Vec<(PathBuf, Vec<String>)>,
+    },
+    CopyToClipboard {
+        text: String,
+    },
+    GenerateIterationReport {
+        iteration: Iteration,
+    },
+    /// Appends a timestamped line to the active story's note or today's daily note
+    Capture {
+        text: String,
+    },
+    ToggleTodo {
+        path: PathBuf,
+        line: usize,
+        text: String,
+    },
+    RefreshSearchIndex,
+    /// Rescans the notes for checkboxes in the background
+    RefreshTodos,
 }
 
 pub async fn execute(
@@ -148,6 +244,29 @@ pub async fn execute(
                 model.cache.active_story = story;
             }
 
+            // a running timer moves to the new active story
+            let active = model.data.active_story.clone();
+            update_time_log(model, |log, now| {
+                Ok(log.follow_active_story(active.as_ref(), now))
+            });
+
+            Ok(())
+        }
+
+        Cmd::ToggleTimer => {
+            let active = model.data.active_story.clone();
+            update_time_log(model, |log, now| {
+                if log.running.is_some() {
+                    log.stop(now);
+                    return Ok(true);
+                }
+
+                let Some(story) = active else {
+                    anyhow::bail!("Select an active story to time first");
+                };
+                log.start((&story).into(), now);
+                Ok(true)
+            });
             Ok(())
         }
 
@@ -159,9 +278,129 @@ pub async fn execute(
             Ok(())
         }
 
-        Cmd::OpenTmuxSession { story_name } => {
-            let session_name = Story::tmux_session_name(&story_name);
-            open_tmux_session(&session_name).await?;
+        Cmd::OpenSession { story } => {
+            let mux = multiplexer::from_config(&model.config);
+            open_story_session(&*mux, &model.config, &model.cache, &story)
+        }
+
+        Cmd::CreateGitWorktree {
+            story,
+            choose_repo,
+            branch_name: linked,
+        } => {
+            let linked_branch = linked.is_some();
+            let branch = linked.unwrap_or_else(|| {
+                branch_name(
+                    &model.config.branch_template,
+                    &story,
+                    model.cache.mention_name.as_deref(),
+                )
+            });
+            if !choose_repo && let Some(repo) = model.cache.story_repo(&story) {
+                let cmd = Cmd::CreateWorktreeInRepo {
+                    repo: repo.to_string(),
+                    branch_name: branch,
+                    linked_branch,
+                    story_id: story.id,
+                    epic_id: story.epic_id,
+                };
+                return Box::pin(execute(cmd, sender, model, api_client)).await;
+            }
+
+            let repos = cached_repos(&model.config);
+            repo_picker::open(&mut model.ui.repo_picker, &story, branch, linked_branch, repos);
+
+            let config = model.config.clone();
+            let handle = tokio::task::spawn_blocking(move || {
+                sender.send(Msg::ReposLoaded(refresh_repo_cache(&config))).ok();
+            });
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::CreateWorktreeInRepo {
+            repo,
+            branch_name,
+            linked_branch,
+            story_id,
+            epic_id,
+        } => {
+            let path = model.config.repositories_directory.join(&repo);
+            model.cache.remember_repo(story_id, epic_id, &repo);
+            model.cache.write().await?;
+
+            let handle = tokio::spawn(async move {
+                if let Err(e) = create_worktree(&path, &branch_name, linked_branch).await {
+                    let info = ErrorInfo::new("Failed to create worktree", e);
+                    sender.send(Msg::Error(info)).ok();
+                }
+            });
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::LoadSessions => {
+            model.ui.session_list.loading = true;
+
+            let mux = multiplexer::from_config(&model.config);
+            let handle = tokio::task::spawn_blocking(move || {
+                let sessions = mux.list_sessions().unwrap_or_else(|e| {
+                    sender
+                        .send(Msg::Error(ErrorInfo::new("Failed to list sessions", e)))
+                        .ok();
+                    Vec::new()
+                });
+                sender.send(Msg::SessionsLoaded(sessions)).ok();
+            });
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::KillSessions { names } => {
+            let mux = multiplexer::from_config(&model.config);
+            let handle = tokio::task::spawn_blocking(move || {
+                for name in &names {
+                    if let Err(e) = mux.kill_session(name) {
+                        let info = ErrorInfo::new("Failed to kill session", e);
+                        sender.send(Msg::Error(info)).ok();
+                    }
+                }
+                sender.send(Msg::SessionsKilled).ok();
+            });
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::SwitchToSession { name } => {
+            multiplexer::from_config(&model.config).attach_session(&name)
+        }
+
+        Cmd::LoadWorktrees => {
+            model.ui.worktree_list.loading = true;
+
+            let config = model.config.clone();
+            let handle = tokio::spawn(async move {
+                sender.send(Msg::WorktreesLoaded(list_worktrees(&config).await)).ok();
+            });
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::RemoveWorktrees {
+            worktrees,
+            delete_branches,
+        } => {
+            let config = model.config.clone();
+            let handle = tokio::spawn(async move {
+                for worktree in &worktrees {
+                    if let Err(e) = remove_worktree(&config, worktree, delete_branches).await {
+                        let info = ErrorInfo::new("Failed to remove worktree", e);
+                        sender.send(Msg::Error(info)).ok();
+                    }
+                }
+                sender.send(Msg::WorktreesRemoved).ok();
+            });
+            model.data.async_handles.push(handle);
             Ok(())
         }
 
@@ -182,19 +421,340 @@ pub async fn execute(
             open::that(&app_url).with_context(|| format!("Failed to open {} in browser", app_url))
         }
 
+        Cmd::RefreshSearchIndex => {
+            let handle = spawn_search_index_refresh(&model.config, sender);
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::RefreshTodos => {
+            let handle = spawn_todos_refresh(&model.config, sender);
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::RenameNote { path, name } => {
+            let result = rename_note(&model.config.notes_dir, &path, &name).map(Some);
+            after_note_change(model, sender, result, "Failed to rename note");
+            Ok(())
+        }
+
+        Cmd::MoveNote { path, category } => {
+            let result = move_note(&model.config.notes_dir, &path, &category).map(Some);
+            after_note_change(model, sender, result, "Failed to move note");
+            Ok(())
+        }
+
+        Cmd::ArchiveNotes { paths } => {
+            let notes_dir = model.config.notes_dir.clone();
+            let failures: Vec<String> = paths
+                .iter()
+                .filter_map(|path| archive_note(&notes_dir, path).err())
+                .map(|e| e.to_string())
+                .collect();
+
+            let result = match failures.is_empty() {
+                true => Ok(None),
+                false => Err(anyhow::anyhow!(failures.join("\n"))),
+            };
+            after_note_change(model, sender, result, "Failed to archive notes");
+            Ok(())
+        }
+
+        Cmd::DeleteNote { path } => {
+            let trash_dir = model.config.cache_dir.join(TRASH_DIR);
+            let trashed = match trash_note(&path, &trash_dir) {
+                Ok(trashed) => trashed,
+                Err(e) => {
+                    model.ui.errors.push(ErrorInfo::new("Failed to delete note", e));
+                    return Ok(());
+                }
+            };
+
+            // only the latest delete can be undone
+            if let Some(previous) = model.ui.notes_list.deleted.replace(trashed.clone())
+                && let Err(e) = previous.purge()
+            {
+                model.ui.errors.push(ErrorInfo::new("Failed to delete note", e));
+            }
+
+            let expiry_sender = sender.clone();
+            let handle = tokio::spawn(async move {
+                tokio::time::sleep(UNDO_WINDOW).await;
+                expiry_sender
+                    .send(Msg::NoteDeleteExpired(trashed.trashed))
+                    .ok();
+            });
+            model.data.async_handles.push(handle);
+
+            after_note_change(model, sender, Ok(None), "Failed to delete note");
+            Ok(())
+        }
+
+        Cmd::UndoDeleteNote => {
+            let Some(deleted) = model.ui.notes_list.deleted.take() else {
+                return Ok(());
+            };
+
+            let result = deleted
+                .restore()
+                .map(|_| Some(deleted.original.clone()))
+                .with_context(|| format!("Deleted note is at {}", deleted.trashed.display()));
+            after_note_change(model, sender, result, "Failed to restore note");
+            Ok(())
+        }
+
+        Cmd::SetNoteTags { path, tags } => {
+            let result = set_note_tags(&path, &tags).map(|_| Some(path));
+            after_note_change(model, sender, result, "Failed to update tags");
+            Ok(())
+        }
+
+        Cmd::MirrorStoryLabels { notes } => {
+            let handle = tokio::task::spawn_blocking(move || {
+                let failures: Vec<String> = notes
+                    .iter()
+                    .filter_map(|(path, tags)| merge_note_tags(path, tags).err())
+                    .map(|e| format!("{:#}", e))
+                    .collect();
+
+                if !failures.is_empty() {
+                    sender
+                        .send(Msg::Error(ErrorInfo::new(
+                            "Failed to mirror story labels",
+                            failures.join("\n"),
+                        )))
+                        .ok();
+                }
+            });
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::CopyToClipboard { text } => {
+            match clipboard::copy(&text) {
+                Ok(()) => {
+                    sender.send(Msg::CopiedToClipboard).ok();
+                }
+                Err(e) => {
+                    let error = ErrorInfo::new("Failed to copy", format!("{:#}", e));
+                    model.ui.errors.push(error);
+                }
+            }
+            Ok(())
+        }
+
+        Cmd::GenerateIterationReport { iteration } => {
+            let api_client = api_client.clone();
+            let notes_dir = model.config.notes_dir.clone();
+            // dummy data has no API behind it, so report on the loaded stories instead
+            let dummy_stories = dummy::is_enabled().then(|| {
+                model
+                    .data
+                    .stories
+                    .iter()
+                    .filter(|s| s.iteration_id == Some(iteration.id))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+
+            let handle = tokio::spawn(async move {
+                let stories = match dummy_stories {
+                    Some(stories) => Ok(stories),
+                    None => api_client.get_iteration_stories(iteration.id).await,
+                };
+
+                let result = stories.and_then(|stories| {
+                    write_iteration_report(&notes_dir, &iteration, &stories)
+                });
+                let msg = match result {
+                    Ok(path) => Msg::IterationReportWritten(path),
+                    Err(e) => Msg::Error(ErrorInfo::new(
+                        "Failed to generate iteration report",
+                        format!("{:#}", e),
+                    )),
+                };
+                sender.send(msg).ok();
+            });
+
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
+        Cmd::Capture { text } => {
+            let target = CaptureTarget::resolve(
+                model.data.active_story.as_ref(),
+                &model.data.stories,
+                model.data.current_iterations.as_deref().unwrap_or(&[]),
+            );
+
+            match capture(&model.config.notes_dir, target, &text, crate::time::now_naive()) {
+                // refresh the notes list and index as if the note was edited
+                Ok(_) => {
+                    sender.send(Msg::NoteOpened).ok();
+                }
+                Err(e) => {
+                    let error = ErrorInfo::new("Failed to capture", format!("{:#}", e));
+                    model.ui.errors.push(error);
+                }
+            }
+            Ok(())
+        }
+
+        Cmd::ToggleTodo { path, line, text } => {
+            if let Err(e) = toggle_todo(&path, line, &text) {
+                model.ui.errors.push(ErrorInfo::new("Failed to toggle todo", e));
+            }
+
+            let handle = spawn_todos_refresh(&model.config, sender);
+            model.data.async_handles.push(handle);
+            Ok(())
+        }
+
         // TUI-suspending commands are handled in main_loop, not here
         Cmd::OpenNote { .. }
         | Cmd::OpenIterationNote { .. }
         | Cmd::OpenEpicNote { .. }
         | Cmd::EditStoryContent { .. }
-        | Cmd::CreateGitWorktree { .. }
         | Cmd::OpenDailyNote { .. }
-        | Cmd::OpenScratchNote { .. } => {
+        | Cmd::OpenScratchNote { .. }
+        | Cmd::OpenNoteAtLine { .. } => {
             unreachable!("TUI-suspending commands should be handled in main_loop")
         }
     }
 }
 
+/// Applies `change` to the time log on disk, so timers started from the CLI aren't lost,
+/// and saves it when `change` returns true.
+fn update_time_log(
+    model: &mut Model,
+    change: impl FnOnce(&mut TimeLog, chrono::DateTime<chrono::Utc>) -> anyhow::Result<bool>,
+) {
+    let cache_dir = model.config.cache_dir.clone();
+    let result = TimeLog::load(&cache_dir).and_then(|mut log| {
+        if change(&mut log, chrono::Utc::now())? {
+            log.save(&cache_dir)?;
+        }
+        Ok(log)
+    });
+
+    match result {
+        Ok(log) => model.data.time_log = log,
+        Err(e) => {
+            let error = ErrorInfo::new("Failed to update timer", format!("{:#}", e));
+            model.ui.errors.push(error);
+        }
+    }
+}
+
+/// Shared follow up for note management commands: surfaces errors, selects `select` if
+/// given, then rescans the notes list and re-indexes so links and backlinks stay current.
+fn after_note_change(
+    model: &mut Model,
+    sender: UnboundedSender<Msg>,
+    result: anyhow::Result<Option<PathBuf>>,
+    error_title: &str,
+) {
+    match result {
+        Ok(Some(path)) => model.ui.notes_list.selected_path = Some(path),
+        Ok(None) => {}
+        Err(e) => model.ui.errors.push(ErrorInfo::new(error_title, format!("{:#}", e))),
+    }
+
+    notes_list::rescan(&mut model.ui.notes_list, &model.config.notes_dir);
+    let handle = spawn_search_index_refresh(&model.config, sender);
+    model.data.async_handles.push(handle);
+}
+
+/// Checks the local git state of stories every `STORY_STATUS_REFRESH`, sending it as
+/// `Msg::StoryStatusesLoaded`.
+pub fn spawn_story_status_refresh(
+    config: &Config,
+    sender: UnboundedSender<Msg>,
+) -> tokio::task::JoinHandle<()> {
+    let config = config.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STORY_STATUS_REFRESH);
+        loop {
+            interval.tick().await;
+            let statuses = story_git_statuses(&config).await;
+            if sender.send(Msg::StoryStatusesLoaded(statuses)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Refreshes the on-disk search index in the background and sends the result as
+/// `Msg::SearchIndexLoaded`.
+pub fn spawn_search_index_refresh(
+    config: &Config,
+    sender: UnboundedSender<Msg>,
+) -> tokio::task::JoinHandle<()> {
+    let notes_dir = config.notes_dir.clone();
+    let cache_dir = config.cache_dir.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let index = SearchIndex::load_and_refresh(&notes_dir, &cache_dir);
+        sender.send(Msg::SearchIndexLoaded(index)).ok();
+    })
+}
+
+/// Scans the notes for checkboxes in the background and sends them as
+/// `Msg::TodosLoaded`.
+pub fn spawn_todos_refresh(
+    config: &Config,
+    sender: UnboundedSender<Msg>,
+) -> tokio::task::JoinHandle<()> {
+    let notes_dir = config.notes_dir.clone();
+
+    tokio::task::spawn_blocking(move || {
+        sender.send(Msg::TodosLoaded(scan_todos(&notes_dir))).ok();
+    })
+}
+
+fn send_sync_status(sender: &UnboundedSender<Msg>, result: anyhow::Result<SyncStatus>) {
+    let msg = match result {
+        Ok(status) => Msg::NotesSynced(status),
+        Err(e) => Msg::Error(ErrorInfo::new("Failed to sync notes", e)),
+    };
+    sender.send(msg).ok();
+}
+
+/// Pulls the notes repository on startup when `git_sync` is enabled.
+pub fn spawn_notes_pull(
+    config: &Config,
+    sender: UnboundedSender<Msg>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    if !config.git_sync {
+        return None;
+    }
+    let notes_dir = config.notes_dir.clone();
+
+    Some(tokio::spawn(async move {
+        send_sync_status(&sender, pull(&notes_dir).await);
+    }))
+}
+
+/// Commits and pushes the notes repository after an editor session when `git_sync` is
+/// enabled. `note` describes what was edited for the commit message.
+pub fn spawn_notes_commit(
+    config: &Config,
+    story_id: Option<i32>,
+    note: &str,
+    sender: UnboundedSender<Msg>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    if !config.git_sync {
+        return None;
+    }
+    let notes_dir = config.notes_dir.clone();
+    let message = commit_message(story_id, note);
+
+    Some(tokio::spawn(async move {
+        send_sync_status(&sender, commit_and_push(&notes_dir, &message).await);
+    }))
+}
+
 pub fn open_in_editor(config: &Config, path: &Path) -> anyhow::Result<()> {
     if path.is_dir() {
         anyhow::bail!("Note path: {} is not a file", path.display());
@@ -219,6 +779,27 @@ pub fn open_in_editor(config: &Config, path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Opens `path` in the editor with the cursor on `line` (zero based), using the `+N`
+/// argument understood by vim, neovim, nano, emacs and kakoune.
+pub fn open_in_editor_at_line(config: &Config, path: &Path, line: usize) -> anyhow::Result<()> {
+    if !path.is_file() {
+        anyhow::bail!("Note path: {} is not a file", path.display());
+    }
+
+    dbg_file!("Opening in editor at line {}: {}", line + 1, path.display());
+
+    let res = Command::new(&config.editor)
+        .arg(format!("+{}", line + 1))
+        .arg(path)
+        .status()?;
+
+    if !res.success() {
+        anyhow::bail!("Failed to open {} in editor", path.display());
+    }
+
+    Ok(())
+}
+
 pub fn open_note_in_editor(
     story_id: i32,
     story_name: String,
@@ -254,6 +835,39 @@ pub fn open_note_in_editor(
     Ok(())
 }
 
+fn iteration_frontmatter(id: i32, name: &str, app_url: &str, today: NaiveDate) -> String {
+    format!(
+        "---\nschema_version: {}\niteration_id: it-{}\niteration_link: {}\niteration_name: {}\n\
+         created: {}\ntype: iteration\ntags: []\n---\n",
+        SCHEMA_VERSION,
+        id,
+        app_url,
+        yaml_string(name),
+        today
+    )
+}
+
+fn epic_frontmatter(id: i32, name: &str, app_url: &str, today: NaiveDate) -> String {
+    format!(
+        "---\nschema_version: {}\nepic_id: ep-{}\nepic_link: {}\nepic_name: {}\ncreated: {}\n\
+         type: epic\ntags: []\n---\n",
+        SCHEMA_VERSION,
+        id,
+        app_url,
+        yaml_string(name),
+        today
+    )
+}
+
+fn scratch_frontmatter(name: &str, today: NaiveDate) -> String {
+    format!(
+        "---\nschema_version: {}\nname: {}\ncreated: {}\ntype: scratch\ntags: []\n---\n",
+        SCHEMA_VERSION,
+        yaml_string(name),
+        today
+    )
+}
+
 pub fn open_iteration_note_in_editor(
     iteration_id: i32,
     iteration_name: String,
@@ -279,10 +893,11 @@ pub fn open_iteration_note_in_editor(
         .open(&path)?;
     let buf = read_to_string(&path)?;
     if buf.is_empty() {
-        let today = crate::time::today();
-        let frontmatter = format!(
-            "---\niteration_id: it-{}\niteration_link: {}\niteration_name: {}\ncreated: {}\n---\n",
-            iteration_id, iteration_app_url, iteration_name, today
+        let frontmatter = iteration_frontmatter(
+            iteration_id,
+            &iteration_name,
+            &iteration_app_url,
+            crate::time::today(),
         );
         f.write_all(frontmatter.as_bytes())?;
     }
@@ -316,11 +931,8 @@ pub fn open_epic_note_in_editor(
         .open(&path)?;
     let buf = read_to_string(&path)?;
     if buf.is_empty() {
-        let today = crate::time::today();
-        let frontmatter = format!(
-            "---\nepic_id: ep-{}\nepic_link: {}\nepic_name: {}\ncreated: {}\n---\n",
-            epic_id, epic_app_url, epic_name, today
-        );
+        let frontmatter =
+            epic_frontmatter(epic_id, &epic_name, &epic_app_url, crate::time::today());
         f.write_all(frontmatter.as_bytes())?;
     }
 
@@ -328,7 +940,11 @@ pub fn open_epic_note_in_editor(
     Ok(())
 }
 
-pub fn open_daily_note_with_frontmatter(config: &Config, path: &Path) -> anyhow::Result<()> {
+pub fn open_daily_note_with_frontmatter(
+    config: &Config,
+    path: &Path,
+    links: &[DailyLink],
+) -> anyhow::Result<()> {
     if path.is_dir() {
         anyhow::bail!("Note path: {} is not a file", path.display());
     }
@@ -345,10 +961,21 @@ pub fn open_daily_note_with_frontmatter(config: &Config, path: &Path) -> anyhow:
     };
 
     if needs_frontmatter {
-        let today = crate::time::today();
-        let frontmatter = format!("---\ncreated: {}\ntype: daily\n---\n", today);
+        // Only dated notes in the daily dir get the rollover, other notes opened through
+        // here just get the bare frontmatter
+        let in_daily_dir = path.parent() == Some(config.notes_dir.join(DAILY_DIR).as_path());
+        let contents = match daily_note_date(path).filter(|_| in_daily_dir) {
+            Some(date) => build_daily_note(path, date, links),
+            None => {
+                let today = crate::time::today();
+                format!(
+                    "---\nschema_version: {}\ncreated: {}\ntype: daily\ntags: []\n---\n",
+                    SCHEMA_VERSION, today
+                )
+            }
+        };
         let mut f = File::create(path)?;
-        f.write_all(frontmatter.as_bytes())?;
+        f.write_all(contents.as_bytes())?;
     }
 
     dbg_file!("Opening daily note in editor: {}", path.display());
@@ -377,11 +1004,7 @@ pub fn open_scratch_note_in_editor(name: &str, path: &Path, config: &Config) ->
     };
 
     if needs_frontmatter {
-        let today = crate::time::today();
-        let frontmatter = format!(
-            "---\nname: {}\ncreated: {}\ntype: scratch\n---\n",
-            name, today
-        );
+        let frontmatter = scratch_frontmatter(name, crate::time::today());
         let mut f = File::create(path)?;
         f.write_all(frontmatter.as_bytes())?;
     }
@@ -389,10 +1012,25 @@ pub fn open_scratch_note_in_editor(name: &str, path: &Path, config: &Config) ->
     open_in_editor(config, path)
 }
 
-pub async fn open_tmux_session(name: &str) -> anyhow::Result<()> {
-    if !session_exists(name).await? {
-        session_create(name).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::note::frontmatter::NoteMeta;
+
+    #[test]
+    fn note_templates_quote_names_with_colons() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+        let url = "https://app.shortcut.com/example/iteration/1";
+
+        let iteration = NoteMeta::parse(&iteration_frontmatter(1, "Sprint 4: login", url, today));
+        assert_eq!(iteration.iteration_name.as_deref(), Some("Sprint 4: login"));
+        assert_eq!(iteration.iteration_number(), Some(1));
+
+        let epic = NoteMeta::parse(&epic_frontmatter(2, "Fix: login flow", url, today));
+        assert_eq!(epic.epic_name.as_deref(), Some("Fix: login flow"));
+
+        let scratch = NoteMeta::parse(&scratch_frontmatter("[Q3] Billing: ideas", today));
+        assert_eq!(scratch.name.as_deref(), Some("[Q3] Billing: ideas"));
+        assert_eq!(scratch.note_type.as_deref(), Some("scratch"));
     }
-    session_attach(name).await?;
-    Ok(())
 }
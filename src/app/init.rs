@@ -6,14 +6,19 @@ use crate::{
     api::ApiClient,
     app::{
         App,
+        cmd::{spawn_notes_pull, spawn_search_index_refresh, spawn_story_status_refresh},
         model::{DataState, Model, UiState},
         msg::Msg,
+        watcher::spawn_notes_watcher,
     },
     cache::Cache,
     config::Config,
+    current_story::detect_story_id,
     dummy,
     error::ErrorInfo,
-    get_user_id,
+    get_mention_name, get_user_id,
+    note::manage::{TRASH_DIR, purge_stale_trash},
+    timer::TimeLog,
 };
 
 impl App {
@@ -25,6 +30,8 @@ impl App {
             return Self::init_with_dummy_data(config, cache).await;
         }
 
+        // before the user ID, as both come from `/member` and this caches them together
+        let mention_name = get_mention_name(&mut cache, &config.api_token).await;
         let api_client = {
             let user_id = get_user_id(cache.user_id, &config.api_token).await?;
             ApiClient::new(config.api_token.to_owned(), user_id)
@@ -37,6 +44,18 @@ impl App {
         let sender_clone = sender.clone();
 
         let mut model = Model::from_cache_and_config(cache, config.clone());
+        model.ui.story_list.selected_story_id = detect_story_id();
+        // only needed for branch names, so don't fail startup over it
+        if let Err(e) = mention_name {
+            model.ui.errors.push(ErrorInfo::new(
+                "Failed to fetch your mention name, branch names will leave out {user}",
+                e,
+            ));
+        }
+
+        start_notes_tasks(&mut model, &config, sender.clone());
+        let status_handle = spawn_story_status_refresh(&config, sender.clone());
+        model.data.async_handles.push(status_handle);
 
         let handles = fetch_info_from_api(api_client.clone(), sender).await;
         model.data.async_handles.extend(handles);
@@ -63,7 +82,7 @@ impl App {
         let iteration = dummy::iteration();
         let stories = dummy::stories();
 
-        let model = Model {
+        let mut model = Model {
             data: DataState {
                 stories: stories.clone(),
                 epics: Vec::new(),
@@ -71,11 +90,20 @@ impl App {
                 active_story: None,
                 async_handles: Vec::new(),
                 iterations: vec![iteration.clone()],
+                search_index: Default::default(),
+                link_graph: Default::default(),
+                notes_watcher: None,
+                time_log: TimeLog::load(&config.cache_dir).unwrap_or_default(),
             },
             ui: UiState::default(),
             config: config.clone(),
             cache,
         };
+        model.ui.story_list.selected_story_id = detect_story_id();
+
+        start_notes_tasks(&mut model, &config, sender.clone());
+        let status_handle = spawn_story_status_refresh(&config, sender.clone());
+        model.data.async_handles.push(status_handle);
 
         // Send messages so UI updates as if data loaded normally
         let _ = sender.send(Msg::IterationsLoaded(vec![iteration.clone()]));
@@ -96,6 +124,30 @@ impl App {
     }
 }
 
+/// Starts the background work that keeps notes data current: the initial search index
+/// refresh (which backlinks are built from) and the notes directory watcher. Notes deleted
+/// in a previous run whose undo window has passed are removed for good.
+fn start_notes_tasks(model: &mut Model, config: &Config, sender: UnboundedSender<Msg>) {
+    if let Err(e) = purge_stale_trash(&config.cache_dir.join(TRASH_DIR)) {
+        model.ui.errors.push(ErrorInfo::new("Failed to empty note trash", e));
+    }
+
+    let index_handle = spawn_search_index_refresh(config, sender.clone());
+    model.data.async_handles.push(index_handle);
+    model.data.async_handles.extend(spawn_notes_pull(config, sender.clone()));
+
+    match spawn_notes_watcher(config.notes_dir.clone(), sender) {
+        Ok((watcher, handle)) => {
+            model.data.notes_watcher = Some(watcher);
+            model.data.async_handles.push(handle);
+        }
+        Err(e) => model
+            .ui
+            .errors
+            .push(ErrorInfo::new("Failed to watch notes directory", e)),
+    }
+}
+
 async fn fetch_info_from_api(api_client: ApiClient, sender: UnboundedSender<Msg>) -> Vec<JoinHandle<()>> {
     let iteration_client = api_client.clone();
     let iteration_sender = sender.clone();
@@ -1,5 +1,6 @@
 use std::fs::read_to_string;
 use std::io::Write;
+use std::path::Path;
 use std::time::Instant;
 
 use anyhow::Result;
@@ -8,7 +9,7 @@ use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::widgets::{Block, Clear, Paragraph, StatefulWidget, WidgetRef, Widget};
+use ratatui::widgets::{Clear, StatefulWidget, WidgetRef, Widget};
 use ratatui::{DefaultTerminal, Frame};
 use tempfile::NamedTempFile;
 use tokio::sync::mpsc;
@@ -16,12 +17,27 @@ use tokio::sync::mpsc;
 use crate::app::pane::action_menu::ActionMenu;
 use crate::dbg_file;
 use crate::error::{ERROR_NOTIFICATION_MAX_HEIGHT, ErrorInfo};
+use crate::note::daily::active_links;
+use crate::note::frontmatter::NoteMeta;
+use crate::view::capture_modal::CaptureModal;
 use crate::view::create_note_modal::CreateNoteModal;
+use crate::timer::format_duration;
+use crate::view::standup_modal::StandupModal;
 use crate::view::description_modal::{DescriptionModal, centered_rect};
 use crate::view::keybinds_panel::KeybindsPanel;
 use crate::view::{EpicListView, IterationListView};
 use crate::view::{navbar::NavBar, notes_list::NotesListView, story_list::StoryListView};
-use crate::worktree::{create_worktree, get_repo_list, select_repo_with_fzf};
+use crate::view::note_action_modal::NoteActionModal;
+use crate::view::note_links::NoteLinksView;
+use crate::view::note_preview::NotePreview;
+use crate::view::note_search::NoteSearchView;
+use crate::view::repo_picker::RepoPicker;
+use crate::view::todo_list::TodoListView;
+use crate::view::session_list::SessionListView;
+use crate::view::worktree_list::WorktreeListView;
+use crate::branch_name::branch_name;
+use crate::multiplexer::{self, open_story_session};
+use crate::worktree::{refresh_repo_cache, select_repo_with_fzf, story_repo};
 use crate::{api::ApiClient, app::model::ViewType, config::Config};
 
 pub mod cmd;
@@ -30,6 +46,7 @@ pub mod model;
 pub mod msg;
 pub mod pane;
 pub mod update;
+pub mod watcher;
 
 pub struct App {
     pub model: model::Model,
@@ -54,9 +71,17 @@ impl App {
                         | cmd::Cmd::OpenIterationNote { .. }
                         | cmd::Cmd::OpenEpicNote { .. }
                         | cmd::Cmd::EditStoryContent { .. }
-                        | cmd::Cmd::CreateGitWorktree { .. }
                         | cmd::Cmd::OpenDailyNote { .. }
-                        | cmd::Cmd::OpenScratchNote { .. } => {
+                        | cmd::Cmd::OpenScratchNote { .. }
+                        | cmd::Cmd::OpenNoteAtLine { .. } => {
+                            self.handle_suspended_cmd(cmd, terminal).await?;
+                        }
+                        cmd::Cmd::CreateGitWorktree { .. } if self.model.config.use_fzf => {
+                            self.handle_suspended_cmd(cmd, terminal).await?;
+                        }
+                        cmd::Cmd::OpenSession { .. } | cmd::Cmd::SwitchToSession { .. }
+                            if !multiplexer::from_config(&self.model.config).inside_session() =>
+                        {
                             self.handle_suspended_cmd(cmd, terminal).await?;
                         }
                         _ => {
@@ -88,6 +113,7 @@ impl App {
                 story_app_url,
                 iteration_app_url,
             } => {
+                let note = story_name.clone();
                 with_suspended_tui(terminal, || {
                     cmd::open_note_in_editor(
                         story_id,
@@ -98,6 +124,7 @@ impl App {
                     )
                 })?;
                 self.sender.send(msg::Msg::NoteOpened).ok();
+                self.commit_notes(Some(story_id), &note);
             }
 
             cmd::Cmd::OpenIterationNote {
@@ -105,6 +132,7 @@ impl App {
                 iteration_name,
                 iteration_app_url,
             } => {
+                let note = format!("iteration note {}", iteration_name);
                 with_suspended_tui(terminal, || {
                     cmd::open_iteration_note_in_editor(
                         iteration_id,
@@ -114,6 +142,7 @@ impl App {
                     )
                 })?;
                 self.sender.send(msg::Msg::NoteOpened).ok();
+                self.commit_notes(None, &note);
             }
 
             cmd::Cmd::EditStoryContent {
@@ -141,21 +170,72 @@ impl App {
                 }
             }
 
-            cmd::Cmd::CreateGitWorktree { branch_name } => {
-                let repos = get_repo_list(&self.model.config).await?;
-                let chosen = match with_suspended_tui(terminal, || select_repo_with_fzf(&repos)) {
-                    Ok(repo) => repo,
-                    Err(e) => {
-                        self.model
-                            .ui
-                            .errors
-                            .push(ErrorInfo::new("Failed to get repo for worktree", e));
-                        return Ok(());
+            cmd::Cmd::CreateGitWorktree {
+                story,
+                choose_repo,
+                branch_name: linked,
+            } => {
+                let remembered = self.model.cache.story_repo(&story).map(str::to_string);
+                let repo = match remembered {
+                    Some(repo) if !choose_repo => repo,
+                    _ => {
+                        let config = self.model.config.clone();
+                        let repos =
+                            tokio::task::spawn_blocking(move || refresh_repo_cache(&config))
+                                .await?;
+                        match with_suspended_tui(terminal, || select_repo_with_fzf(&repos)) {
+                            Ok(repo) => repo,
+                            Err(e) => {
+                                self.model
+                                    .ui
+                                    .errors
+                                    .push(ErrorInfo::new("Failed to get repo for worktree", e));
+                                return Ok(());
+                            }
+                        }
                     }
                 };
 
-                let path = self.model.config.repositories_directory.join(chosen);
-                create_worktree(&path, &branch_name).await?;
+                let linked_branch = linked.is_some();
+                let branch_name = linked.unwrap_or_else(|| {
+                    branch_name(
+                        &self.model.config.branch_template,
+                        &story,
+                        self.model.cache.mention_name.as_deref(),
+                    )
+                });
+                let cmd = cmd::Cmd::CreateWorktreeInRepo {
+                    repo,
+                    branch_name,
+                    linked_branch,
+                    story_id: story.id,
+                    epic_id: story.epic_id,
+                };
+                cmd::execute(cmd, self.sender.clone(), &mut self.model, &self.api_client).await?;
+            }
+
+            cmd::Cmd::OpenSession { story } => {
+                let mux = multiplexer::from_config(&self.model.config);
+                let config = &self.model.config;
+                let cache = &self.model.cache;
+                if let Err(e) = with_suspended_tui(terminal, || {
+                    open_story_session(&*mux, config, cache, &story)
+                }) {
+                    self.model
+                        .ui
+                        .errors
+                        .push(ErrorInfo::new("Failed to open session", e));
+                }
+            }
+
+            cmd::Cmd::SwitchToSession { name } => {
+                let mux = multiplexer::from_config(&self.model.config);
+                if let Err(e) = with_suspended_tui(terminal, || mux.attach_session(&name)) {
+                    self.model
+                        .ui
+                        .errors
+                        .push(ErrorInfo::new("Failed to attach to session", e));
+                }
             }
 
             cmd::Cmd::OpenEpicNote {
@@ -163,6 +243,7 @@ impl App {
                 epic_name,
                 epic_app_url,
             } => {
+                let note = format!("epic note {}", epic_name);
                 with_suspended_tui(terminal, || {
                     cmd::open_epic_note_in_editor(
                         epic_id,
@@ -172,13 +253,19 @@ impl App {
                     )
                 })?;
                 self.sender.send(msg::Msg::NoteOpened).ok();
+                self.commit_notes(None, &note);
             }
 
             cmd::Cmd::OpenDailyNote { path } => {
+                let links = active_links(
+                    &self.model.data.stories,
+                    self.model.data.current_iterations.as_deref().unwrap_or(&[]),
+                );
                 with_suspended_tui(terminal, || {
-                    cmd::open_daily_note_with_frontmatter(&self.model.config, &path)
+                    cmd::open_daily_note_with_frontmatter(&self.model.config, &path, &links)
                 })?;
                 self.sender.send(msg::Msg::NoteOpened).ok();
+                self.commit_note_at(&path);
             }
 
             cmd::Cmd::OpenScratchNote { path, name } => {
@@ -186,6 +273,15 @@ impl App {
                     cmd::open_scratch_note_in_editor(&name, &path, &self.model.config)
                 })?;
                 self.sender.send(msg::Msg::NoteOpened).ok();
+                self.commit_note_at(&path);
+            }
+
+            cmd::Cmd::OpenNoteAtLine { path, line } => {
+                with_suspended_tui(terminal, || {
+                    cmd::open_in_editor_at_line(&self.model.config, &path, line)
+                })?;
+                self.sender.send(msg::Msg::NoteOpened).ok();
+                self.commit_note_at(&path);
             }
 
             _ => unreachable!("Non-suspending command passed to handle_suspended_cmd"),
@@ -194,6 +290,26 @@ impl App {
         Ok(())
     }
 
+    /// Commits and pushes the notes after an editor session, see `Config::git_sync`.
+    fn commit_notes(&mut self, story_id: Option<i32>, note: &str) {
+        let handle =
+            cmd::spawn_notes_commit(&self.model.config, story_id, note, self.sender.clone());
+        self.model.data.async_handles.extend(handle);
+    }
+
+    /// Like `commit_notes`, naming the note by its path and story frontmatter.
+    fn commit_note_at(&mut self, path: &Path) {
+        let story_id = read_to_string(path)
+            .ok()
+            .and_then(|contents| NoteMeta::parse(&contents).story_number());
+        let note = path
+            .strip_prefix(&self.model.config.notes_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        self.commit_notes(story_id, &note);
+    }
+
     async fn poll_for_message(&mut self) -> Result<Option<msg::Msg>> {
         use crossterm::event::{self, Event, KeyEventKind};
         use std::time::Duration;
@@ -236,11 +352,17 @@ impl App {
 
         // Render navbar
         let has_stories = !self.model.data.stories.is_empty();
+        let timer = self.model.data.time_log.running.as_ref().map(|running| {
+            let elapsed = chrono::Utc::now() - running.start;
+            format!("sc-{} {}", running.story.id, format_duration(elapsed))
+        });
         let navbar = NavBar::new(
             self.model.ui.active_view,
             self.model.ui.loading,
             has_stories,
             tick,
+            timer,
+            self.model.ui.sync_conflicts.is_some(),
         );
 
         navbar.render_ref(chunks[0], frame.buffer_mut());
@@ -262,8 +384,56 @@ impl App {
             }
 
             ViewType::Notes => {
-                let notes_view = NotesListView::new(&self.model.ui.notes_list);
-                notes_view.render_ref(chunks[1], frame.buffer_mut());
+                let panes = Layout::horizontal([
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(60),
+                ])
+                .split(chunks[1]);
+
+                let notes_view = NotesListView::new(&self.model.ui.notes_list, &self.model.data.stories);
+                notes_view.render_ref(panes[0], frame.buffer_mut());
+
+                let notes_list = &mut self.model.ui.notes_list;
+                let links_view = NoteLinksView::new(
+                    &self.model.data.link_graph,
+                    notes_list.selected_path.as_deref(),
+                    notes_list.selected_link,
+                );
+                let right = Layout::vertical([
+                    Constraint::Min(0),
+                    Constraint::Length(links_view.height(panes[1].height / 3)),
+                ])
+                .split(panes[1]);
+
+                let title = notes_list
+                    .selected_path
+                    .as_ref()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                NotePreview::new(&title, &notes_list.preview).render(
+                    right[0],
+                    frame.buffer_mut(),
+                    &mut notes_list.preview_scroll,
+                );
+                links_view.render_ref(right[1], frame.buffer_mut());
+            }
+
+            ViewType::Todos => {
+                let todo_view = TodoListView::new(&self.model.ui.todo_list);
+                todo_view.render_ref(chunks[1], frame.buffer_mut());
+            }
+
+            ViewType::Worktrees => {
+                let worktree_view =
+                    WorktreeListView::new(&self.model.ui.worktree_list, &self.model.data.stories);
+                worktree_view.render_ref(chunks[1], frame.buffer_mut());
+            }
+
+            ViewType::Sessions => {
+                let session_view =
+                    SessionListView::new(&self.model.ui.session_list, &self.model.data.stories);
+                session_view.render_ref(chunks[1], frame.buffer_mut());
             }
 
             ViewType::Iterations => {
@@ -283,9 +453,8 @@ impl App {
             }
 
             ViewType::Search => {
-                // Placeholder for future views
-                let placeholder = Paragraph::new("Coming soon...").block(Block::bordered());
-                placeholder.render(chunks[1], frame.buffer_mut());
+                let search_view = NoteSearchView::new(&self.model.ui.note_search);
+                search_view.render_ref(chunks[1], frame.buffer_mut());
             }
         }
 
@@ -310,7 +479,15 @@ impl App {
             let area = centered_rect(80, 80, frame.area());
             Clear.render(area, frame.buffer_mut());
 
-            let modal = DescriptionModal::new(story);
+            let graph = &self.model.data.link_graph;
+            let mentioned_in = graph
+                .story_backlinks(story.id)
+                .into_iter()
+                .map(|path| graph.title(path))
+                .collect();
+
+            let repo = story_repo(&self.model.config, &self.model.cache, story);
+            let modal = DescriptionModal::new(story, mentioned_in, repo);
             modal.render(
                 area,
                 frame.buffer_mut(),
@@ -326,6 +503,37 @@ impl App {
             modal.render_ref(area, frame.buffer_mut());
         }
 
+        if self.model.ui.standup_modal.is_showing {
+            let area = frame.area();
+            Clear.render(centered_rect(60, 60, area), frame.buffer_mut());
+            let modal = StandupModal::new(&self.model.ui.standup_modal);
+            modal.render_ref(area, frame.buffer_mut());
+        }
+
+        if self.model.ui.capture_modal.is_showing {
+            let area = frame.area();
+            Clear.render(centered_rect(50, 30, area), frame.buffer_mut());
+            let modal = CaptureModal::new(
+                &self.model.ui.capture_modal,
+                self.model.data.active_story.as_ref(),
+            );
+            modal.render_ref(area, frame.buffer_mut());
+        }
+
+        if self.model.ui.repo_picker.is_showing {
+            let area = frame.area();
+            Clear.render(centered_rect(60, 60, area), frame.buffer_mut());
+            let picker = RepoPicker::new(&self.model.ui.repo_picker);
+            picker.render_ref(area, frame.buffer_mut());
+        }
+
+        if self.model.ui.note_action_modal.is_showing {
+            let area = frame.area();
+            Clear.render(centered_rect(50, 40, area), frame.buffer_mut());
+            let modal = NoteActionModal::new(&self.model.ui.note_action_modal);
+            modal.render_ref(area, frame.buffer_mut());
+        }
+
         // Render keybinds panel (above description modal, below errors)
         if self.model.ui.show_keybinds_panel {
             KeybindsPanel.render(frame.area(), frame.buffer_mut());
@@ -2,7 +2,15 @@ use throbber_widgets_tui::ThrobberState;
 use tokio::task::JoinHandle;
 use tui_scrollview::ScrollViewState;
 
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::SystemTime,
+};
+
+use chrono::NaiveDate;
+
+use notify::RecommendedWatcher;
 
 use crate::{
     api::{epic::EpicSlim, iteration::Iteration, story::Story},
@@ -10,6 +18,15 @@ use crate::{
     cache::Cache,
     config::Config,
     error::ErrorInfo,
+    note::{
+        links::LinkGraph,
+        manage::TrashedNote,
+        search::{SearchHit, SearchIndex},
+        todo::TodoGroup,
+    },
+    timer::TimeLog,
+    multiplexer::SessionInfo,
+    worktree::{GitStatus, WorktreeInfo},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -40,8 +57,11 @@ pub enum ViewType {
     Stories, // Current default: story list
     Epics,      // Future: browse all epics
     Notes,      // Future: browse notes directory
-    Search,     // Future: search across stories/notes
+    Todos,      // open checkboxes across all notes
+    Search,     // full-text search across notes
     Iterations, // browse iterations
+    Worktrees,  // git worktrees of stories
+    Sessions,   // tmux or zellij sessions of stories
 }
 
 impl ViewType {
@@ -50,7 +70,10 @@ impl ViewType {
         ViewType::Stories,
         ViewType::Iterations,
         ViewType::Notes,
+        ViewType::Todos,
         ViewType::Epics,
+        ViewType::Worktrees,
+        ViewType::Sessions,
         ViewType::Search,
     ];
 
@@ -58,8 +81,11 @@ impl ViewType {
         match self {
             ViewType::Stories => ViewType::Iterations,
             ViewType::Iterations => ViewType::Notes,
-            ViewType::Notes => ViewType::Epics,
-            ViewType::Epics => ViewType::Search,
+            ViewType::Notes => ViewType::Todos,
+            ViewType::Todos => ViewType::Epics,
+            ViewType::Epics => ViewType::Worktrees,
+            ViewType::Worktrees => ViewType::Sessions,
+            ViewType::Sessions => ViewType::Search,
             ViewType::Search => ViewType::Stories,
         }
     }
@@ -67,8 +93,11 @@ impl ViewType {
     pub fn prev(self) -> Self {
         match self {
             ViewType::Stories => ViewType::Search,
-            ViewType::Search => ViewType::Epics,
-            ViewType::Epics => ViewType::Notes,
+            ViewType::Search => ViewType::Sessions,
+            ViewType::Sessions => ViewType::Worktrees,
+            ViewType::Worktrees => ViewType::Epics,
+            ViewType::Epics => ViewType::Todos,
+            ViewType::Todos => ViewType::Notes,
             ViewType::Notes => ViewType::Iterations,
             ViewType::Iterations => ViewType::Stories,
         }
@@ -79,8 +108,11 @@ impl ViewType {
             ViewType::Stories => "Stories",
             ViewType::Epics => "Epics",
             ViewType::Notes => "Notes",
+            ViewType::Todos => "Todos",
             ViewType::Search => "Search",
             ViewType::Iterations => "Iterations",
+            ViewType::Worktrees => "Worktrees",
+            ViewType::Sessions => "Sessions",
         }
     }
 }
@@ -101,6 +133,11 @@ pub struct DataState {
     pub current_iterations: Option<Vec<Iteration>>,
     pub active_story: Option<Story>,
     pub async_handles: Vec<JoinHandle<()>>,
+    pub search_index: SearchIndex,
+    pub link_graph: LinkGraph,
+    /// Keeps the notes directory watcher alive, `None` if it couldn't be started
+    pub notes_watcher: Option<RecommendedWatcher>,
+    pub time_log: TimeLog,
 }
 
 #[derive(Default, Debug)]
@@ -115,13 +152,23 @@ pub struct UiState {
     pub active_view: ViewType,
     pub story_list: StoryListState,
     pub notes_list: NotesListState,
+    pub todo_list: TodoListState,
+    pub worktree_list: WorktreeListState,
+    pub session_list: SessionListState,
+    pub note_search: NoteSearchState,
     pub iteration_list: IterationListState,
     pub epic_list: EpicListState,
     pub action_menu: ActionMenuState,
     pub description_modal: DescriptionModalState,
     pub create_note_modal: CreateNoteModalState,
+    pub capture_modal: CaptureModalState,
+    pub repo_picker: RepoPickerState,
+    pub standup_modal: StandupModalState,
+    pub note_action_modal: NoteActionModalState,
     pub show_keybinds_panel: bool,
     pub errors: Vec<ErrorInfo>,
+    /// Files the last notes sync conflicted in, shown until a sync succeeds
+    pub sync_conflicts: Option<Vec<String>>,
     pub loading: LoadingState,
     pub throbber_state: ThrobberState,
 }
@@ -144,6 +191,8 @@ pub struct EpicListState {
 pub struct StoryListState {
     pub selected_story_id: Option<i32>,
     pub show_finished: bool,
+    /// Local git state of stories with a worktree or branch, by story ID
+    pub git_statuses: HashMap<i32, GitStatus>,
 }
 
 impl Default for StoryListState {
@@ -151,18 +200,88 @@ impl Default for StoryListState {
         Self {
             selected_story_id: Default::default(),
             show_finished: true,
+            git_statuses: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoteSort {
+    #[default]
+    Name,
+    Modified,
+    Created,
+}
+
+impl NoteSort {
+    pub fn next(self) -> NoteSort {
+        match self {
+            NoteSort::Name => NoteSort::Modified,
+            NoteSort::Modified => NoteSort::Created,
+            NoteSort::Created => NoteSort::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NoteSort::Name => "name",
+            NoteSort::Modified => "modified",
+            NoteSort::Created => "created",
         }
     }
 }
 
+/// Per-note details shown in and used to sort the notes list.
+#[derive(Clone, Debug, Default)]
+pub struct NoteInfo {
+    /// Title from the frontmatter (`story_name`, `iteration_name`, `epic_name` or `name`)
+    pub title: Option<String>,
+    pub story_id: Option<i32>,
+    pub created: Option<NaiveDate>,
+    pub modified: Option<SystemTime>,
+    /// Lowercased tags from the frontmatter
+    pub tags: Vec<String>,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct NotesListState {
     pub selected_path: Option<PathBuf>,
+    /// Category whose header is selected, only set when no note is selected
+    pub selected_category: Option<&'static str>,
+    pub collapsed: HashSet<&'static str>,
+    pub sort: NoteSort,
+    pub info: HashMap<PathBuf, NoteInfo>,
+    /// Only notes with every one of these tags are listed
+    pub tag_filter: Vec<String>,
     pub daily_notes: Vec<PathBuf>,
     pub story_notes: Vec<PathBuf>,
     pub iteration_notes: Vec<PathBuf>,
     pub epic_notes: Vec<PathBuf>,
     pub scratch_notes: Vec<PathBuf>,
+    /// Contents of the selected note, shown in the preview pane
+    pub preview: String,
+    pub preview_scroll: ScrollViewState,
+    /// Index into the selected note's outgoing links followed by its backlinks
+    pub selected_link: Option<usize>,
+    /// Most recently deleted note, restorable until the undo window closes
+    pub deleted: Option<TrashedNote>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TodoListState {
+    pub groups: Vec<TodoGroup>,
+    /// Note path and line of the selected checkbox
+    pub selected: Option<(PathBuf, usize)>,
+    pub show_done: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NoteSearchState {
+    pub search_query: String,
+    pub search_active: bool,
+    pub results: Vec<SearchHit>,
+    pub selected: Option<usize>,
+    pub indexing: bool,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -171,6 +290,102 @@ pub struct CreateNoteModalState {
     pub input: String,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct StandupModalState {
+    pub is_showing: bool,
+    pub text: String,
+    /// Set once the text was copied, until the modal is reopened
+    pub copied: bool,
+}
+
+/// A list of things created for stories, such as worktrees or sessions, which can be
+/// cleaned up once the user confirms. See `pane::cleanup_list`.
+#[derive(Clone, Debug)]
+pub struct CleanupListState<T, P> {
+    pub items: Vec<T>,
+    pub selected: usize,
+    pub loading: bool,
+    /// The cleanup waiting for the user to confirm it
+    pub pending: Option<P>,
+}
+
+impl<T, P> Default for CleanupListState<T, P> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            selected: 0,
+            loading: false,
+            pending: None,
+        }
+    }
+}
+
+pub type WorktreeListState = CleanupListState<WorktreeInfo, WorktreeRemoval>;
+
+#[derive(Clone, Debug)]
+pub struct WorktreeRemoval {
+    pub worktrees: Vec<WorktreeInfo>,
+    pub delete_branches: bool,
+}
+
+/// Pending are the sessions to kill.
+pub type SessionListState = CleanupListState<SessionInfo, Vec<SessionInfo>>;
+
+#[derive(Clone, Debug, Default)]
+pub struct RepoPickerState {
+    pub is_showing: bool,
+    /// Branch the worktree is created for once a repo is picked
+    pub branch_name: String,
+    /// Whether `branch_name` is linked to the story, and so has to be on origin
+    pub linked_branch: bool,
+    /// Story the picked repo is remembered for
+    pub story_id: i32,
+    pub epic_id: Option<i32>,
+    pub query: String,
+    /// Repos relative to `repositories_directory`
+    pub repos: Vec<String>,
+    /// Index into the repos matching `query`
+    pub selected: usize,
+    /// Set while repos are rediscovered in the background
+    pub refreshing: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CaptureModalState {
+    pub is_showing: bool,
+    pub input: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoteAction {
+    Rename,
+    Delete,
+    Move,
+    Archive,
+    /// Archive every note of a completed story or ended iteration
+    ArchiveFinished,
+    /// Add or remove tags on the selected note
+    EditTags,
+    /// Pick the tags the notes list is filtered by
+    FilterTags,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NoteActionModalState {
+    pub is_showing: bool,
+    pub action: Option<NoteAction>,
+    /// Notes the action applies to
+    pub paths: Vec<PathBuf>,
+    /// New name when renaming
+    pub input: String,
+    /// Index into `note::manage::CATEGORIES` when moving
+    pub category: usize,
+    /// Tags of the note, or of the filter, being edited
+    pub tags: Vec<String>,
+    /// Tags used anywhere in the notes directory, for completion
+    pub known_tags: Vec<String>,
+}
+
 impl StoryListState {
     /// Returns the index of the selected story in the given slice, if it exists.
     pub fn selected_index(&self, stories: &[Story]) -> Option<usize> {
@@ -195,6 +410,10 @@ impl Model {
                 active_story: cache.active_story.clone(),
                 async_handles: Vec::new(),
                 iterations: cache.iterations.clone(),
+                search_index: SearchIndex::default(),
+                link_graph: LinkGraph::default(),
+                notes_watcher: None,
+                time_log: TimeLog::load(&config.cache_dir).unwrap_or_default(),
             },
             ui: UiState::default(),
             config,
@@ -1,19 +1,39 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use crossterm::event::KeyEvent;
 
 use crate::api::{epic::EpicSlim, iteration::Iteration, story::Story};
-use crate::app::model::ViewType;
+use crate::app::model::{NoteAction, ViewType};
+use crate::app::watcher::NotesChange;
 use crate::error::ErrorInfo;
+use crate::note::{search::SearchIndex, sync::SyncStatus, todo::TodoGroup};
+use crate::multiplexer::SessionInfo;
+use crate::worktree::{GitStatus, WorktreeInfo};
 
 #[derive(Debug, Clone)]
 pub enum Msg {
     KeyPressed(KeyEvent),
     StoryList(StoryListMsg),
     NotesList(NotesListMsg),
+    TodoList(TodoListMsg),
+    WorktreeList(WorktreeListMsg),
+    SessionList(SessionListMsg),
+    NoteSearch(NoteSearchMsg),
     IterationList(IterationListMsg),
     EpicList(EpicListMsg),
     ActionMenu(ActionMenuMsg),
     DescriptionModal(DescriptionModalMsg),
     CreateNoteModal(CreateNoteModalMsg),
+    CaptureModal(CaptureModalMsg),
+    RepoPicker(RepoPickerMsg),
+    StandupModal(StandupModalMsg),
+    NoteActionModal(NoteActionModalMsg),
+    /// The undo window for a deleted note has closed
+    NoteDeleteExpired(PathBuf),
+    CopiedToClipboard,
+    /// An iteration report was written to this path
+    IterationReportWritten(PathBuf),
     StoriesLoaded {
         stories: Vec<Story>,
         from_cache: bool,
@@ -21,6 +41,21 @@ pub enum Msg {
     EpicsLoaded(Vec<EpicSlim>),
     IterationsLoaded(Vec<Iteration>),
     AllIterationsLoaded(Vec<Iteration>),
+    SearchIndexLoaded(SearchIndex),
+    /// Notes were rescanned for checkboxes
+    TodosLoaded(Vec<TodoGroup>),
+    /// Repos were discovered under `repositories_directory`
+    ReposLoaded(Vec<String>),
+    WorktreesLoaded(Vec<WorktreeInfo>),
+    WorktreesRemoved,
+    /// Local git state of the stories with a worktree or branch, by story ID
+    StoryStatusesLoaded(HashMap<i32, GitStatus>),
+    SessionsLoaded(Vec<SessionInfo>),
+    SessionsKilled,
+    /// A pull or commit of the git-backed notes directory finished
+    NotesSynced(SyncStatus),
+    /// Notes were created, edited or removed on disk
+    NotesChanged(NotesChange),
     SwitchToView(ViewType),
     NoteOpened,
     ToggleActionMenu,
@@ -55,6 +90,35 @@ pub enum NotesListMsg {
     FocusNext,
     FocusPrev,
     OpenNote,
+    PreviewPageDown,
+    PreviewPageUp,
+    NextLink,
+    PrevLink,
+    FollowLink,
+    UndoDelete,
+    ToggleCollapse,
+    CycleSort,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TodoListMsg {
+    FocusNext,
+    FocusPrev,
+    Toggle,
+    OpenAtLine,
+    ToggleShowDone,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum NoteSearchMsg {
+    FocusNext,
+    FocusPrev,
+    OpenResult,
+    ActivateSearch,
+    DeactivateSearch,
+    SearchInput(char),
+    SearchBackspace,
+    ClearSearch,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -62,6 +126,7 @@ pub enum IterationListMsg {
     FocusNext,
     FocusPrev,
     OpenNote,
+    GenerateReport,
     ActivateSearch,
     DeactivateSearch,
     SearchInput(char),
@@ -93,6 +158,21 @@ pub enum DescriptionModalMsg {
     ScrollToBottom,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum NoteActionModalMsg {
+    Open(NoteAction),
+    Close,
+    TextInput(char),
+    TextBackspace,
+    NextCategory,
+    PrevCategory,
+    /// Complete the tag being typed from the top suggestion
+    CompleteTag,
+    /// Add the typed tag, or remove it if it's already there
+    ToggleTag,
+    Accept,
+}
+
 #[derive(Debug, Clone)]
 pub enum CreateNoteModalMsg {
     Open,
@@ -101,3 +181,55 @@ pub enum CreateNoteModalMsg {
     TextBackspace,
     Accept,
 }
+
+#[derive(Debug, Clone)]
+pub enum StandupModalMsg {
+    Open,
+    Close,
+    Copy,
+}
+
+#[derive(Debug, Clone)]
+pub enum CaptureModalMsg {
+    Open,
+    Close,
+    TextInput(char),
+    TextBackspace,
+    Accept,
+}
+
+#[derive(Debug, Clone)]
+pub enum RepoPickerMsg {
+    Close,
+    TextInput(char),
+    TextBackspace,
+    FocusNext,
+    FocusPrev,
+    Accept,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum WorktreeListMsg {
+    FocusNext,
+    FocusPrev,
+    Refresh,
+    RemoveSelected,
+    /// Remove the worktrees of every completed story
+    RemoveFinished,
+    ToggleDeleteBranches,
+    ConfirmRemoval,
+    CancelRemoval,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SessionListMsg {
+    FocusNext,
+    FocusPrev,
+    Refresh,
+    SwitchTo,
+    KillSelected,
+    /// Kill the sessions of every completed story
+    KillFinished,
+    ConfirmKill,
+    CancelKill,
+}
@@ -15,6 +15,8 @@ use crate::{
         model::{DataState, UiState},
         msg::ActionMenuMsg,
     },
+    branch_name::branch_name,
+    config::Config,
     error::ErrorInfo,
     navkey,
     view::ActionItemWidget,
@@ -106,6 +108,9 @@ pub enum ActionMenuItem {
     OpenTmux,
     SetActive,
     CreateGitWorktree,
+    CreateWorktreeInOtherRepo,
+    CheckoutLinkedBranch,
+    CopyBranchName,
     OpenInBrowser,
 }
 
@@ -114,6 +119,9 @@ impl ActionMenuItem {
         Self::OpenNote,
         Self::OpenIterationNote,
         Self::CreateGitWorktree,
+        Self::CreateWorktreeInOtherRepo,
+        Self::CheckoutLinkedBranch,
+        Self::CopyBranchName,
         Self::OpenTmux,
         Self::EditDescription,
         Self::SetActive,
@@ -129,9 +137,12 @@ impl ActionMenuItem {
             Self::OpenNote => "Open Note",
             Self::OpenIterationNote => "Open Iteration Note",
             Self::EditDescription => "Edit Description",
-            Self::OpenTmux => "Open Tmux Session",
+            Self::OpenTmux => "Open Session",
             Self::SetActive => "Toggle Active Story",
             Self::CreateGitWorktree => "Create git worktree",
+            Self::CreateWorktreeInOtherRepo => "Create worktree in another repo",
+            Self::CheckoutLinkedBranch => "Check out linked branch in worktree",
+            Self::CopyBranchName => "Copy branch name",
             Self::OpenInBrowser => "Open ticket in browser",
         }
     }
@@ -140,6 +151,8 @@ impl ActionMenuItem {
 pub fn update(
     ui_state: &mut UiState,
     data_state: &DataState,
+    config: &Config,
+    mention_name: Option<&str>,
     msg: ActionMenuMsg,
     story: &Story,
 ) -> Vec<Cmd> {
@@ -222,8 +235,8 @@ pub fn update(
                 }
 
                 ActionMenuItem::OpenTmux => {
-                    vec![Cmd::OpenTmuxSession {
-                        story_name: story.name.clone(),
+                    vec![Cmd::OpenSession {
+                        story: story.clone(),
                     }]
                 }
 
@@ -232,9 +245,39 @@ pub fn update(
                 }
 
                 ActionMenuItem::CreateGitWorktree => {
-                    // TODO: figure out how to get actual branch name
                     vec![Cmd::CreateGitWorktree {
-                        branch_name: "feat/branchname".to_string(),
+                        story: story.clone(),
+                        choose_repo: false,
+                        branch_name: None,
+                    }]
+                }
+
+                ActionMenuItem::CreateWorktreeInOtherRepo => {
+                    vec![Cmd::CreateGitWorktree {
+                        story: story.clone(),
+                        choose_repo: true,
+                        branch_name: None,
+                    }]
+                }
+
+                ActionMenuItem::CheckoutLinkedBranch => match story.linked_branch() {
+                    Some(branch) => vec![Cmd::CreateGitWorktree {
+                        story: story.clone(),
+                        choose_repo: false,
+                        branch_name: Some(branch.to_string()),
+                    }],
+                    None => {
+                        ui_state.errors.push(ErrorInfo::new(
+                            "No linked branch",
+                            "This story has no open pull request or branch to check out",
+                        ));
+                        vec![Cmd::None]
+                    }
+                },
+
+                ActionMenuItem::CopyBranchName => {
+                    vec![Cmd::CopyToClipboard {
+                        text: branch_name(&config.branch_template, story, mention_name),
                     }]
                 }
 
@@ -0,0 +1,48 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{cmd::Cmd, model::CaptureModalState, msg::CaptureModalMsg};
+
+pub fn update(state: &mut CaptureModalState, msg: CaptureModalMsg) -> Vec<Cmd> {
+    match msg {
+        CaptureModalMsg::Open => {
+            state.is_showing = true;
+            state.input.clear();
+            vec![Cmd::None]
+        }
+
+        CaptureModalMsg::Close => {
+            state.is_showing = false;
+            vec![Cmd::None]
+        }
+
+        CaptureModalMsg::TextInput(c) => {
+            state.input.push(c);
+            vec![Cmd::None]
+        }
+
+        CaptureModalMsg::TextBackspace => {
+            state.input.pop();
+            vec![Cmd::None]
+        }
+
+        CaptureModalMsg::Accept => {
+            if state.input.trim().is_empty() {
+                return vec![Cmd::None];
+            }
+            state.is_showing = false;
+            vec![Cmd::Capture {
+                text: std::mem::take(&mut state.input),
+            }]
+        }
+    }
+}
+
+pub fn key_to_msg(key: KeyEvent) -> Option<CaptureModalMsg> {
+    match key.code {
+        KeyCode::Esc => Some(CaptureModalMsg::Close),
+        KeyCode::Enter => Some(CaptureModalMsg::Accept),
+        KeyCode::Backspace => Some(CaptureModalMsg::TextBackspace),
+        KeyCode::Char(c) => Some(CaptureModalMsg::TextInput(c)),
+        _ => None,
+    }
+}
@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::model::CleanupListState;
+
+/// Replaces the listed items, keeping the selection in range.
+pub fn set_items<T, P>(state: &mut CleanupListState<T, P>, items: Vec<T>) {
+    state.items = items;
+    state.loading = false;
+    state.selected = state.selected.min(state.items.len().saturating_sub(1));
+}
+
+pub fn focus_next<T, P>(state: &mut CleanupListState<T, P>) {
+    if !state.items.is_empty() {
+        state.selected = (state.selected + 1) % state.items.len();
+    }
+}
+
+pub fn focus_prev<T, P>(state: &mut CleanupListState<T, P>) {
+    if !state.items.is_empty() {
+        state.selected = state
+            .selected
+            .checked_sub(1)
+            .unwrap_or(state.items.len() - 1);
+    }
+}
+
+/// The selected item, as a list so it can be cleaned up like a batch.
+pub fn selected<T: Clone, P>(state: &CleanupListState<T, P>) -> Vec<T> {
+    state
+        .items
+        .get(state.selected)
+        .cloned()
+        .into_iter()
+        .collect()
+}
+
+/// Asks the user to confirm cleaning up `items`, nothing to confirm when there are none.
+pub fn ask<T, P>(
+    state: &mut CleanupListState<T, P>,
+    items: Vec<T>,
+    pending: impl FnOnce(Vec<T>) -> P,
+) {
+    if !items.is_empty() {
+        state.pending = Some(pending(items));
+    }
+}
+
+/// The confirmed cleanup, the list is loading until it has been done and reloaded.
+pub fn confirm<T, P>(state: &mut CleanupListState<T, P>) -> Option<P> {
+    let pending = state.pending.take();
+    state.loading |= pending.is_some();
+    pending
+}
+
+pub fn cancel<T, P>(state: &mut CleanupListState<T, P>) {
+    state.pending = None;
+}
+
+/// `confirm` or `cancel` for the keys answering a confirmation prompt.
+pub fn confirm_key_to_msg<M>(key: KeyEvent, confirm: M, cancel: M) -> Option<M> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => Some(confirm),
+        KeyCode::Char('n') | KeyCode::Esc => Some(cancel),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirms_only_non_empty_cleanups_once() {
+        let mut state = CleanupListState::<i32, Vec<i32>>::default();
+        set_items(&mut state, vec![1, 2, 3]);
+        focus_prev(&mut state);
+        assert_eq!(selected(&state), vec![3]);
+
+        ask(&mut state, Vec::new(), |items| items);
+        assert!(state.pending.is_none());
+
+        let items = selected(&state);
+        ask(&mut state, items, |items| items);
+        assert_eq!(confirm(&mut state), Some(vec![3]));
+        assert!(state.loading);
+        assert_eq!(confirm(&mut state), None);
+
+        set_items(&mut state, vec![1]);
+        assert_eq!((state.selected, state.loading), (0, false));
+    }
+}
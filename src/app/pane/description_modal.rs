@@ -88,6 +88,13 @@ mod tests {
             name: "Test Story".to_string(),
             description: "Test description".to_string(),
             completed: false,
+            started: false,
+            labels: vec![],
+            estimate: None,
+            blocked: false,
+            moved_at: None,
+            story_type: "feature".to_string(),
+            pull_requests: vec![],
             branches: vec![],
             comments: vec![],
             epic_id: None,
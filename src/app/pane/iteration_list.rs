@@ -110,6 +110,22 @@ pub fn update(
             }
         }
 
+        IterationListMsg::GenerateReport => {
+            let selected = state.selected_id.and_then(|id| {
+                current_iterations
+                    .iter()
+                    .find(|it| it.id == id)
+                    .or_else(|| all_iterations.iter().find(|it| it.id == id))
+            });
+
+            match selected {
+                Some(iteration) => vec![Cmd::GenerateIterationReport {
+                    iteration: iteration.clone(),
+                }],
+                None => vec![Cmd::None],
+            }
+        }
+
         IterationListMsg::ActivateSearch => {
             state.search_active = true;
             vec![Cmd::None]
@@ -158,6 +174,7 @@ pub fn key_to_msg(key: KeyEvent) -> Option<IterationListMsg> {
         navkey!(down) => Some(IterationListMsg::FocusNext),
         navkey!(up) => Some(IterationListMsg::FocusPrev),
         KeyCode::Enter => Some(IterationListMsg::OpenNote),
+        KeyCode::Char('r') => Some(IterationListMsg::GenerateReport),
         _ => None,
     }
 }
@@ -1,7 +1,16 @@
 pub mod action_menu;
+pub mod capture_modal;
+pub mod cleanup_list;
 pub mod create_note_modal;
 pub mod description_modal;
 pub mod epic_list;
 pub mod iteration_list;
+pub mod note_action_modal;
+pub mod note_search;
 pub mod notes_list;
+pub mod repo_picker;
+pub mod session_list;
+pub mod standup_modal;
 pub mod story_list;
+pub mod todo_list;
+pub mod worktree_list;
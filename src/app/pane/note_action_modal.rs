@@ -0,0 +1,228 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    app::{
+        cmd::Cmd,
+        model::{DataState, NoteAction, NoteActionModalState, NotesListState},
+        msg::NoteActionModalMsg,
+        pane::notes_list,
+    },
+    config::Config,
+    navkey,
+    note::{
+        manage::{ARCHIVE_DIR, CATEGORIES, finished_notes},
+        tags::normalize_tag,
+    },
+};
+
+/// Maximum number of tag completions shown below the input.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Known tags starting with the typed input that aren't selected yet.
+pub fn suggestions(state: &NoteActionModalState) -> Vec<&str> {
+    let prefix = normalize_tag(&state.input);
+    state
+        .known_tags
+        .iter()
+        .filter(|tag| tag.starts_with(&prefix) && !state.tags.contains(tag))
+        .take(MAX_SUGGESTIONS)
+        .map(String::as_str)
+        .collect()
+}
+
+pub fn update(
+    state: &mut NoteActionModalState,
+    notes_list: &mut NotesListState,
+    data: &DataState,
+    config: &Config,
+    msg: NoteActionModalMsg,
+) -> Vec<Cmd> {
+    match msg {
+        NoteActionModalMsg::Open(action) => {
+            let paths = match action {
+                NoteAction::ArchiveFinished => finished_notes(
+                    &config.notes_dir,
+                    &data.stories,
+                    &data.iterations,
+                    crate::time::today(),
+                ),
+                NoteAction::FilterTags => Vec::new(),
+                _ => match &notes_list.selected_path {
+                    Some(path) => vec![path.clone()],
+                    None => return vec![Cmd::None],
+                },
+            };
+
+            state.input = match action {
+                NoteAction::Rename => paths
+                    .first()
+                    .and_then(|p| p.file_stem())
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+
+            // start the category picker on the note's current category
+            state.category = paths
+                .first()
+                .and_then(|p| p.parent())
+                .and_then(|dir| dir.file_name())
+                .and_then(|name| CATEGORIES.iter().position(|c| name == *c))
+                .unwrap_or(0);
+
+            state.tags = match action {
+                NoteAction::EditTags => paths
+                    .first()
+                    .and_then(|p| notes_list.info.get(p))
+                    .map(|info| info.tags.clone())
+                    .unwrap_or_default(),
+                NoteAction::FilterTags => notes_list.tag_filter.clone(),
+                _ => Vec::new(),
+            };
+            state.known_tags = data.search_index.all_tags();
+
+            state.action = Some(action);
+            state.paths = paths;
+            state.is_showing = true;
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::Close => {
+            state.is_showing = false;
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::TextInput(c) => {
+            state.input.push(c);
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::TextBackspace => {
+            let editing_tags = matches!(
+                state.action,
+                Some(NoteAction::EditTags | NoteAction::FilterTags)
+            );
+            if editing_tags && state.input.is_empty() {
+                state.tags.pop();
+            } else {
+                state.input.pop();
+            }
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::CompleteTag => {
+            if let Some(tag) = suggestions(state).first() {
+                state.input = tag.to_string();
+            }
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::ToggleTag => {
+            let tag = normalize_tag(&state.input);
+            state.input.clear();
+            if tag.is_empty() {
+                return vec![Cmd::None];
+            }
+
+            if let Some(idx) = state.tags.iter().position(|t| *t == tag) {
+                state.tags.remove(idx);
+            } else {
+                state.tags.push(tag);
+            }
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::NextCategory => {
+            state.category = (state.category + 1) % CATEGORIES.len();
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::PrevCategory => {
+            state.category = state
+                .category
+                .checked_sub(1)
+                .unwrap_or(CATEGORIES.len() - 1);
+            vec![Cmd::None]
+        }
+
+        NoteActionModalMsg::Accept => {
+            state.is_showing = false;
+
+            if state.action == Some(NoteAction::FilterTags) {
+                notes_list::set_tag_filter(notes_list, state.tags.clone());
+                return vec![Cmd::None];
+            }
+
+            let Some(path) = state.paths.first().cloned() else {
+                return vec![Cmd::None];
+            };
+
+            match state.action {
+                Some(NoteAction::Rename) if !state.input.trim().is_empty() => {
+                    vec![Cmd::RenameNote {
+                        path,
+                        name: state.input.trim().to_string(),
+                    }]
+                }
+                Some(NoteAction::Delete) => vec![Cmd::DeleteNote { path }],
+                Some(NoteAction::EditTags) => vec![Cmd::SetNoteTags {
+                    path,
+                    tags: state.tags.clone(),
+                }],
+                Some(NoteAction::Move) => vec![Cmd::MoveNote {
+                    path,
+                    category: CATEGORIES[state.category].to_string(),
+                }],
+                Some(NoteAction::Archive | NoteAction::ArchiveFinished) => {
+                    // archived notes can't be archived again
+                    let paths = state
+                        .paths
+                        .iter()
+                        .filter(|p| !p.starts_with(config.notes_dir.join(ARCHIVE_DIR)))
+                        .cloned()
+                        .collect();
+                    vec![Cmd::ArchiveNotes { paths }]
+                }
+                _ => vec![Cmd::None],
+            }
+        }
+    }
+}
+
+pub fn key_to_msg(state: &NoteActionModalState, key: KeyEvent) -> Option<NoteActionModalMsg> {
+    match state.action? {
+        NoteAction::Rename => match key.code {
+            KeyCode::Esc => Some(NoteActionModalMsg::Close),
+            KeyCode::Enter => Some(NoteActionModalMsg::Accept),
+            KeyCode::Backspace => Some(NoteActionModalMsg::TextBackspace),
+            KeyCode::Char(c) => Some(NoteActionModalMsg::TextInput(c)),
+            _ => None,
+        },
+
+        NoteAction::EditTags | NoteAction::FilterTags => match key.code {
+            KeyCode::Esc => Some(NoteActionModalMsg::Close),
+            KeyCode::Enter if state.input.trim().is_empty() => Some(NoteActionModalMsg::Accept),
+            KeyCode::Enter => Some(NoteActionModalMsg::ToggleTag),
+            KeyCode::Tab => Some(NoteActionModalMsg::CompleteTag),
+            KeyCode::Backspace => Some(NoteActionModalMsg::TextBackspace),
+            KeyCode::Char(c) => Some(NoteActionModalMsg::TextInput(c)),
+            _ => None,
+        },
+
+        NoteAction::Move => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(NoteActionModalMsg::Close),
+            KeyCode::Enter => Some(NoteActionModalMsg::Accept),
+            navkey!(down) => Some(NoteActionModalMsg::NextCategory),
+            navkey!(up) => Some(NoteActionModalMsg::PrevCategory),
+            _ => None,
+        },
+
+        NoteAction::Delete | NoteAction::Archive | NoteAction::ArchiveFinished => match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(NoteActionModalMsg::Accept),
+            KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => {
+                Some(NoteActionModalMsg::Close)
+            }
+            _ => None,
+        },
+    }
+}
@@ -0,0 +1,94 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    app::{cmd::Cmd, model::NoteSearchState, msg::NoteSearchMsg},
+    navkey,
+    note::search::{Query, SearchIndex},
+};
+
+/// Re-runs the current query against `index`, keeping the selection in range.
+pub fn rerun(state: &mut NoteSearchState, index: &SearchIndex) {
+    state.results = index.search(&Query::parse(&state.search_query));
+    state.selected = match state.selected {
+        _ if state.results.is_empty() => None,
+        Some(idx) => Some(idx.min(state.results.len() - 1)),
+        None => Some(0),
+    };
+}
+
+pub fn update(state: &mut NoteSearchState, index: &SearchIndex, msg: NoteSearchMsg) -> Vec<Cmd> {
+    match msg {
+        NoteSearchMsg::FocusNext => {
+            if state.results.is_empty() {
+                return vec![Cmd::None];
+            }
+
+            state.selected = Some(match state.selected {
+                Some(idx) => (idx + 1) % state.results.len(),
+                None => 0,
+            });
+            vec![Cmd::None]
+        }
+
+        NoteSearchMsg::FocusPrev => {
+            if state.results.is_empty() {
+                return vec![Cmd::None];
+            }
+
+            state.selected = Some(match state.selected {
+                Some(0) | None => state.results.len() - 1,
+                Some(idx) => idx - 1,
+            });
+            vec![Cmd::None]
+        }
+
+        NoteSearchMsg::OpenResult => {
+            match state.selected.and_then(|idx| state.results.get(idx)) {
+                Some(hit) => vec![Cmd::OpenNoteAtLine {
+                    path: hit.path.clone(),
+                    line: hit.line,
+                }],
+                None => vec![Cmd::None],
+            }
+        }
+
+        NoteSearchMsg::ActivateSearch => {
+            state.search_active = true;
+            vec![Cmd::None]
+        }
+
+        NoteSearchMsg::DeactivateSearch => {
+            state.search_active = false;
+            vec![Cmd::None]
+        }
+
+        NoteSearchMsg::SearchInput(c) => {
+            state.search_query.push(c);
+            state.selected = None;
+            rerun(state, index);
+            vec![Cmd::None]
+        }
+
+        NoteSearchMsg::SearchBackspace => {
+            state.search_query.pop();
+            rerun(state, index);
+            vec![Cmd::None]
+        }
+
+        NoteSearchMsg::ClearSearch => {
+            state.search_query.clear();
+            state.search_active = false;
+            rerun(state, index);
+            vec![Cmd::None]
+        }
+    }
+}
+
+pub fn key_to_msg(key: KeyEvent) -> Option<NoteSearchMsg> {
+    match key.code {
+        navkey!(down) => Some(NoteSearchMsg::FocusNext),
+        navkey!(up) => Some(NoteSearchMsg::FocusPrev),
+        KeyCode::Enter => Some(NoteSearchMsg::OpenResult),
+        _ => None,
+    }
+}
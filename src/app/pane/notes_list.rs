@@ -1,16 +1,32 @@
-use std::path::{Path, PathBuf};
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+};
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tui_scrollview::ScrollViewState;
 
 use crate::{
-    app::{cmd::Cmd, msg::NotesListMsg},
+    app::{cmd::Cmd, model::NoteInfo, model::NoteSort, msg::NotesListMsg, watcher::NotesChange},
     navkey,
+    note::{
+        daily::daily_note_date, frontmatter::NoteMeta, links::LinkGraph, manage::CATEGORIES,
+        report::is_report,
+    },
 };
 
 pub use crate::app::model::NotesListState;
 
-/// Scans one subdirectory of the notes directory and returns `.md` files sorted descending.
-fn scan_subdir(notes_dir: &Path, subdir: &str) -> Vec<PathBuf> {
+/// A row of the notes tree, either a category header or a note inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteRow<'a> {
+    Header(&'static str),
+    Note(&'a PathBuf),
+}
+
+/// Scans one subdirectory of the notes directory and returns `.md` files sorted descending,
+/// leaving out generated reports.
+pub fn scan_subdir(notes_dir: &Path, subdir: &str) -> Vec<PathBuf> {
     let dir = notes_dir.join(subdir);
     let mut notes = Vec::new();
 
@@ -27,6 +43,9 @@ fn scan_subdir(notes_dir: &Path, subdir: &str) -> Vec<PathBuf> {
         if path.extension().and_then(|e| e.to_str()) != Some("md") {
             continue;
         }
+        if is_report(&path) {
+            continue;
+        }
         notes.push(path);
     }
 
@@ -52,64 +71,383 @@ pub fn scan_notes(
     (daily, stories, iterations, epics, scratch)
 }
 
-/// Returns a flat list of all notes in display order.
+/// Reads the frontmatter and modification time of a note.
+pub fn note_info(path: &Path) -> NoteInfo {
+    let meta = std::fs::read_to_string(path)
+        .map(|contents| NoteMeta::parse(&contents))
+        .unwrap_or_default();
+
+    NoteInfo {
+        title: meta.title(),
+        story_id: meta.story_number(),
+        created: meta.created.or_else(|| daily_note_date(path)),
+        modified: std::fs::metadata(path).and_then(|m| m.modified()).ok(),
+        tags: meta.tags.iter().map(|t| t.to_lowercase()).collect(),
+    }
+}
+
+pub fn category_notes<'a>(state: &'a NotesListState, category: &str) -> &'a [PathBuf] {
+    match category {
+        "daily" => &state.daily_notes,
+        "stories" => &state.story_notes,
+        "iterations" => &state.iteration_notes,
+        "epics" => &state.epic_notes,
+        "scratch" => &state.scratch_notes,
+        _ => &[],
+    }
+}
+
+fn category_notes_mut<'a>(
+    state: &'a mut NotesListState,
+    category: &str,
+) -> Option<&'a mut Vec<PathBuf>> {
+    match category {
+        "daily" => Some(&mut state.daily_notes),
+        "stories" => Some(&mut state.story_notes),
+        "iterations" => Some(&mut state.iteration_notes),
+        "epics" => Some(&mut state.epic_notes),
+        "scratch" => Some(&mut state.scratch_notes),
+        _ => None,
+    }
+}
+
+/// Whether a note has every tag in the tag filter.
+pub fn matches_tag_filter(state: &NotesListState, path: &PathBuf) -> bool {
+    state.tag_filter.is_empty()
+        || state
+            .info
+            .get(path)
+            .is_some_and(|info| state.tag_filter.iter().all(|tag| info.tags.contains(tag)))
+}
+
+/// Notes of one category that pass the tag filter, in the current sort order.
+fn sorted_notes<'a>(state: &'a NotesListState, category: &str) -> Vec<&'a PathBuf> {
+    let mut notes: Vec<&PathBuf> = category_notes(state, category)
+        .iter()
+        .filter(|path| matches_tag_filter(state, path))
+        .collect();
+    let info = |path: &PathBuf| state.info.get(path);
+
+    match state.sort {
+        // daily notes are named by date, so newest first reads best
+        NoteSort::Name if category == "daily" => notes.sort_by_key(|p| Reverse(p.file_name())),
+        NoteSort::Name => notes.sort_by_cached_key(|p| {
+            info(p)
+                .and_then(|i| i.title.clone())
+                .unwrap_or_else(|| {
+                    p.file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .to_lowercase()
+        }),
+        NoteSort::Modified => notes.sort_by_key(|p| Reverse(info(p).and_then(|i| i.modified))),
+        NoteSort::Created => notes.sort_by_key(|p| Reverse(info(p).and_then(|i| i.created))),
+    }
+
+    notes
+}
+
+/// The rows of the notes tree in display order. Categories without notes passing the
+/// tag filter are left out and collapsed categories only show their header.
+pub fn visible_rows(state: &NotesListState) -> Vec<NoteRow<'_>> {
+    let mut rows = Vec::new();
+
+    for category in CATEGORIES {
+        let notes = sorted_notes(state, category);
+        if notes.is_empty() {
+            continue;
+        }
+
+        rows.push(NoteRow::Header(category));
+        if !state.collapsed.contains(category) {
+            rows.extend(notes.into_iter().map(NoteRow::Note));
+        }
+    }
+
+    rows
+}
+
+/// Index of the selected row, if it is visible.
+pub fn selected_row(state: &NotesListState, rows: &[NoteRow]) -> Option<usize> {
+    rows.iter().position(|row| match row {
+        NoteRow::Header(category) => state.selected_category == Some(*category),
+        NoteRow::Note(path) => state.selected_path.as_ref() == Some(*path),
+    })
+}
+
+/// Returns a flat list of all notes, regardless of collapsed categories.
 fn all_notes(state: &NotesListState) -> Vec<&PathBuf> {
-    state
-        .daily_notes
+    CATEGORIES
         .iter()
-        .chain(state.story_notes.iter())
-        .chain(state.iteration_notes.iter())
-        .chain(state.epic_notes.iter())
-        .chain(state.scratch_notes.iter())
+        .flat_map(|category| category_notes(state, category))
         .collect()
 }
 
-pub fn update(state: &mut NotesListState, msg: NotesListMsg) -> Vec<Cmd> {
+/// Selects the row at `idx`, loading the note's preview when it changed.
+fn select_row(state: &mut NotesListState, idx: usize) {
+    let (path, category) = match visible_rows(state).get(idx) {
+        Some(NoteRow::Note(path)) => (Some((*path).clone()), None),
+        Some(NoteRow::Header(category)) => (None, Some(*category)),
+        None => (None, None),
+    };
+
+    let changed = path != state.selected_path;
+    state.selected_path = path;
+    state.selected_category = category;
+    if changed {
+        load_preview(state);
+    }
+}
+
+/// Selects `path`, expanding its category if it is collapsed.
+fn select_path(state: &mut NotesListState, path: PathBuf) {
+    if let Some(category) = CATEGORIES
+        .iter()
+        .find(|category| category_notes(state, category).contains(&path))
+    {
+        state.collapsed.remove(category);
+    }
+    if !matches_tag_filter(state, &path) {
+        state.tag_filter.clear();
+    }
+
+    state.selected_path = Some(path);
+    state.selected_category = None;
+    load_preview(state);
+}
+
+/// Keeps the selection on a visible row after the list changed, falling back to the
+/// row now at `previous_idx` or the first row.
+fn fix_selection(state: &mut NotesListState, previous_idx: Option<usize>) {
+    let rows = visible_rows(state);
+    if selected_row(state, &rows).is_some() {
+        return;
+    }
+
+    let idx = match previous_idx {
+        Some(idx) => idx.min(rows.len().saturating_sub(1)),
+        None => rows
+            .iter()
+            .position(|row| matches!(row, NoteRow::Note(_)))
+            .unwrap_or(0),
+    };
+    select_row(state, idx);
+}
+
+/// Filters the list down to notes with every one of `tags`, moving the selection if
+/// the selected note is filtered out.
+pub fn set_tag_filter(state: &mut NotesListState, tags: Vec<String>) {
+    state.tag_filter = tags;
+    fix_selection(state, None);
+}
+
+/// Rescans the notes directory, keeping the selection if the note still exists and
+/// falling back to the first note otherwise.
+pub fn rescan(state: &mut NotesListState, notes_dir: &Path) {
+    let (daily, stories, iterations, epics, scratch) = scan_notes(notes_dir);
+    state.daily_notes = daily;
+    state.story_notes = stories;
+    state.iteration_notes = iterations;
+    state.epic_notes = epics;
+    state.scratch_notes = scratch;
+
+    state.info = all_notes(state)
+        .into_iter()
+        .map(|path| (path.clone(), note_info(path)))
+        .collect();
+
+    fix_selection(state, None);
+    load_preview(state);
+}
+
+/// Applies a background rescan from the notes watcher. The selection is kept when the
+/// note still exists, otherwise the note now at its position is selected.
+pub fn apply_change(state: &mut NotesListState, change: &NotesChange) {
+    let previous_idx = selected_row(state, &visible_rows(state));
+
+    for (category, notes) in &change.categories {
+        if let Some(list) = category_notes_mut(state, category) {
+            *list = notes.clone();
+        }
+    }
+    for (path, info) in &change.info {
+        state.info.insert(path.clone(), info.clone());
+    }
+    let listed: Vec<PathBuf> = all_notes(state).into_iter().cloned().collect();
+    state.info.retain(|path, _| listed.contains(path));
+
+    if let Some(selected) = state.selected_path.as_ref()
+        && listed.contains(selected)
+    {
+        // reload an edited note in place so the preview doesn't jump back to the top
+        if change.paths.contains(selected) {
+            state.preview = std::fs::read_to_string(selected).unwrap_or_default();
+        }
+        return;
+    }
+
+    fix_selection(state, previous_idx);
+}
+
+/// Reads the selected note into the preview and resets its scroll position.
+pub fn load_preview(state: &mut NotesListState) {
+    state.preview = state
+        .selected_path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    state.preview_scroll = ScrollViewState::default();
+    state.selected_link = None;
+}
+
+/// Targets of the selected note's outgoing links followed by its backlinks, in the
+/// order the links panel shows them. Unresolved links have no target.
+pub fn link_targets(state: &NotesListState, graph: &LinkGraph) -> Vec<Option<PathBuf>> {
+    let Some(path) = state.selected_path.as_ref() else {
+        return Vec::new();
+    };
+
+    graph
+        .outgoing(path)
+        .iter()
+        .map(|link| link.path.clone())
+        .chain(graph.backlinks(path).into_iter().map(|p| Some(p.clone())))
+        .collect()
+}
+
+pub fn update(state: &mut NotesListState, graph: &LinkGraph, msg: NotesListMsg) -> Vec<Cmd> {
     match msg {
         NotesListMsg::FocusNext => {
-            let notes = all_notes(state);
-            if notes.is_empty() {
+            let rows = visible_rows(state);
+            if rows.is_empty() {
                 return vec![Cmd::None];
             }
 
-            let current_idx = state
-                .selected_path
-                .as_ref()
-                .and_then(|sel| notes.iter().position(|p| *p == sel));
-
-            let next_idx = match current_idx {
-                Some(idx) => (idx + 1) % notes.len(),
+            let next_idx = match selected_row(state, &rows) {
+                Some(idx) => (idx + 1) % rows.len(),
                 None => 0,
             };
 
-            state.selected_path = Some(notes[next_idx].clone());
+            select_row(state, next_idx);
             vec![Cmd::None]
         }
 
         NotesListMsg::FocusPrev => {
-            let notes = all_notes(state);
-            if notes.is_empty() {
+            let rows = visible_rows(state);
+            if rows.is_empty() {
                 return vec![Cmd::None];
             }
 
-            let current_idx = state
-                .selected_path
-                .as_ref()
-                .and_then(|sel| notes.iter().position(|p| *p == sel));
-
-            let prev_idx = match current_idx {
-                Some(0) => notes.len() - 1,
+            let prev_idx = match selected_row(state, &rows) {
+                Some(0) | None => rows.len() - 1,
                 Some(idx) => idx - 1,
-                None => notes.len() - 1,
             };
 
-            state.selected_path = Some(notes[prev_idx].clone());
+            select_row(state, prev_idx);
+            vec![Cmd::None]
+        }
+
+        NotesListMsg::ToggleCollapse => {
+            let category = state.selected_category.or_else(|| {
+                let selected = state.selected_path.as_ref()?;
+                CATEGORIES
+                    .into_iter()
+                    .find(|category| category_notes(state, category).contains(selected))
+            });
+
+            let Some(category) = category else {
+                return vec![Cmd::None];
+            };
+
+            if !state.collapsed.remove(category) {
+                state.collapsed.insert(category);
+                // the selected note is hidden now, so select its header instead
+                state.selected_path = None;
+                state.selected_category = Some(category);
+                load_preview(state);
+            }
+            vec![Cmd::None]
+        }
+
+        NotesListMsg::CycleSort => {
+            state.sort = state.sort.next();
             vec![Cmd::None]
         }
 
         NotesListMsg::OpenNote => {
             if let Some(ref path) = state.selected_path {
                 vec![Cmd::OpenDailyNote { path: path.clone() }]
+            } else if state.selected_category.is_some() {
+                update(state, graph, NotesListMsg::ToggleCollapse)
+            } else {
+                vec![Cmd::None]
+            }
+        }
+
+        NotesListMsg::PreviewPageDown => {
+            state.preview_scroll.scroll_page_down();
+            vec![Cmd::None]
+        }
+
+        NotesListMsg::PreviewPageUp => {
+            state.preview_scroll.scroll_page_up();
+            vec![Cmd::None]
+        }
+
+        NotesListMsg::NextLink => {
+            let count = link_targets(state, graph).len();
+            if count == 0 {
+                return vec![Cmd::None];
+            }
+
+            state.selected_link = Some(match state.selected_link {
+                Some(idx) => (idx + 1) % count,
+                None => 0,
+            });
+            vec![Cmd::None]
+        }
+
+        NotesListMsg::PrevLink => {
+            let count = link_targets(state, graph).len();
+            if count == 0 {
+                return vec![Cmd::None];
+            }
+
+            state.selected_link = Some(match state.selected_link {
+                Some(0) | None => count - 1,
+                Some(idx) => idx - 1,
+            });
+            vec![Cmd::None]
+        }
+
+        NotesListMsg::FollowLink => {
+            let target = state
+                .selected_link
+                .and_then(|idx| link_targets(state, graph).into_iter().nth(idx))
+                .flatten();
+
+            let Some(target) = target else {
+                return vec![Cmd::None];
+            };
+
+            // notes shown in the list are selected in place, anything else (e.g. notes
+            // in nested folders) is opened directly
+            if all_notes(state).contains(&&target) {
+                select_path(state, target);
+                vec![Cmd::None]
+            } else {
+                vec![Cmd::OpenNoteAtLine {
+                    path: target,
+                    line: 0,
+                }]
+            }
+        }
+
+        NotesListMsg::UndoDelete => {
+            if state.deleted.is_some() {
+                vec![Cmd::UndoDeleteNote]
             } else {
                 vec![Cmd::None]
             }
@@ -122,6 +460,20 @@ pub fn key_to_msg(key: KeyEvent) -> Option<NotesListMsg> {
         navkey!(down) => Some(NotesListMsg::FocusNext),
         navkey!(up) => Some(NotesListMsg::FocusPrev),
         KeyCode::Enter => Some(NotesListMsg::OpenNote),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(NotesListMsg::PreviewPageDown)
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(NotesListMsg::PreviewPageUp)
+        }
+        KeyCode::PageDown => Some(NotesListMsg::PreviewPageDown),
+        KeyCode::PageUp => Some(NotesListMsg::PreviewPageUp),
+        KeyCode::Char(']') => Some(NotesListMsg::NextLink),
+        KeyCode::Char('[') => Some(NotesListMsg::PrevLink),
+        KeyCode::Char('o') => Some(NotesListMsg::FollowLink),
+        KeyCode::Char('u') => Some(NotesListMsg::UndoDelete),
+        KeyCode::Char(' ') => Some(NotesListMsg::ToggleCollapse),
+        KeyCode::Char('s') => Some(NotesListMsg::CycleSort),
         _ => None,
     }
 }
@@ -0,0 +1,164 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+
+use crate::{
+    api::story::Story,
+    app::{cmd::Cmd, model::RepoPickerState, msg::RepoPickerMsg},
+};
+
+/// Repos matching the query, best match first, with the indices of the matched chars.
+pub fn matches(state: &RepoPickerState) -> Vec<(&str, Vec<usize>)> {
+    if state.query.is_empty() {
+        return state
+            .repos
+            .iter()
+            .map(|r| (r.as_str(), Vec::new()))
+            .collect();
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, &str, Vec<usize>)> = state
+        .repos
+        .iter()
+        .filter_map(|repo| {
+            let (score, indices) = matcher.fuzzy_indices(repo, &state.query)?;
+            Some((score, repo.as_str(), indices))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    scored
+        .into_iter()
+        .map(|(_, repo, indices)| (repo, indices))
+        .collect()
+}
+
+fn clamp_selection(state: &mut RepoPickerState) {
+    let len = matches(state).len();
+    state.selected = state.selected.min(len.saturating_sub(1));
+}
+
+/// Opens the picker for `story`'s `branch_name`, starting with the cached repos.
+pub fn open(
+    state: &mut RepoPickerState,
+    story: &Story,
+    branch_name: String,
+    linked_branch: bool,
+    repos: Vec<String>,
+) {
+    *state = RepoPickerState {
+        is_showing: true,
+        branch_name,
+        linked_branch,
+        story_id: story.id,
+        epic_id: story.epic_id,
+        repos,
+        refreshing: true,
+        ..Default::default()
+    };
+}
+
+/// Replaces the repos with freshly discovered ones, keeping the query.
+pub fn set_repos(state: &mut RepoPickerState, repos: Vec<String>) {
+    state.repos = repos;
+    state.refreshing = false;
+    clamp_selection(state);
+}
+
+pub fn update(state: &mut RepoPickerState, msg: RepoPickerMsg) -> Vec<Cmd> {
+    match msg {
+        RepoPickerMsg::Close => {
+            state.is_showing = false;
+        }
+
+        RepoPickerMsg::TextInput(c) => {
+            state.query.push(c);
+            state.selected = 0;
+        }
+
+        RepoPickerMsg::TextBackspace => {
+            state.query.pop();
+            state.selected = 0;
+        }
+
+        RepoPickerMsg::FocusNext => {
+            state.selected += 1;
+            clamp_selection(state);
+        }
+
+        RepoPickerMsg::FocusPrev => {
+            state.selected = state.selected.saturating_sub(1);
+        }
+
+        RepoPickerMsg::Accept => {
+            let Some((repo, _)) = matches(state).get(state.selected).cloned() else {
+                return vec![Cmd::None];
+            };
+            let repo = repo.to_string();
+            state.is_showing = false;
+            return vec![Cmd::CreateWorktreeInRepo {
+                repo,
+                branch_name: std::mem::take(&mut state.branch_name),
+                linked_branch: state.linked_branch,
+                story_id: state.story_id,
+                epic_id: state.epic_id,
+            }];
+        }
+    }
+
+    vec![Cmd::None]
+}
+
+pub fn key_to_msg(key: KeyEvent) -> Option<RepoPickerMsg> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => Some(RepoPickerMsg::Close),
+        KeyCode::Enter => Some(RepoPickerMsg::Accept),
+        KeyCode::Down => Some(RepoPickerMsg::FocusNext),
+        KeyCode::Up => Some(RepoPickerMsg::FocusPrev),
+        KeyCode::Char('n') if ctrl => Some(RepoPickerMsg::FocusNext),
+        KeyCode::Char('p') if ctrl => Some(RepoPickerMsg::FocusPrev),
+        KeyCode::Backspace => Some(RepoPickerMsg::TextBackspace),
+        KeyCode::Char(c) => Some(RepoPickerMsg::TextInput(c)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dummy;
+
+    #[test]
+    fn matches_rank_best_first_and_accept_picks_selected() {
+        let mut state = RepoPickerState::default();
+        let story = dummy::stories().remove(0);
+        open(
+            &mut state,
+            &story,
+            "feat/login".to_string(),
+            true,
+            vec![
+                "work/api".to_string(),
+                "web".to_string(),
+                "work/web-app".to_string(),
+            ],
+        );
+
+        for c in "web".chars() {
+            update(&mut state, RepoPickerMsg::TextInput(c));
+        }
+        let names: Vec<&str> = matches(&state).into_iter().map(|(repo, _)| repo).collect();
+        assert_eq!(names, vec!["web", "work/web-app"]);
+
+        update(&mut state, RepoPickerMsg::FocusNext);
+        update(&mut state, RepoPickerMsg::FocusNext);
+        let cmds = update(&mut state, RepoPickerMsg::Accept);
+        assert!(matches!(
+            cmds.as_slice(),
+            [Cmd::CreateWorktreeInRepo { repo, branch_name, linked_branch: true, story_id, .. }]
+                if repo == "work/web-app" && branch_name == "feat/login" && *story_id == story.id
+        ));
+        assert!(!state.is_showing);
+    }
+}
@@ -0,0 +1,142 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    api::story::Story,
+    app::{
+        cmd::Cmd,
+        model::SessionListState,
+        msg::SessionListMsg,
+        pane::cleanup_list::{self, ask, confirm_key_to_msg},
+    },
+    navkey,
+    multiplexer::SessionInfo,
+};
+
+/// The story a session belongs to. Sessions created before the story ID was stored fall
+/// back to matching the session name.
+pub fn session_story<'a>(session: &SessionInfo, stories: &'a [Story]) -> Option<&'a Story> {
+    match session.story_id {
+        Some(id) => stories.iter().find(|s| s.id == id),
+        None => stories
+            .iter()
+            .find(|s| Story::tmux_session_name(&s.name) == session.name),
+    }
+}
+
+/// Sessions whose story has been completed.
+pub fn finished_sessions(state: &SessionListState, stories: &[Story]) -> Vec<SessionInfo> {
+    state
+        .items
+        .iter()
+        .filter(|session| session_story(session, stories).is_some_and(|s| s.completed))
+        .cloned()
+        .collect()
+}
+
+/// Lists the sessions by name.
+pub fn set_sessions(state: &mut SessionListState, mut sessions: Vec<SessionInfo>) {
+    sessions.sort_by(|a, b| a.name.cmp(&b.name));
+    cleanup_list::set_items(state, sessions);
+}
+
+pub fn update(state: &mut SessionListState, stories: &[Story], msg: SessionListMsg) -> Vec<Cmd> {
+    match msg {
+        SessionListMsg::FocusNext => cleanup_list::focus_next(state),
+        SessionListMsg::FocusPrev => cleanup_list::focus_prev(state),
+        SessionListMsg::Refresh => return vec![Cmd::LoadSessions],
+
+        SessionListMsg::SwitchTo => {
+            if let Some(session) = state.items.get(state.selected) {
+                return vec![Cmd::SwitchToSession {
+                    name: session.name.clone(),
+                }];
+            }
+        }
+
+        SessionListMsg::KillSelected => {
+            let selected = cleanup_list::selected(state);
+            ask(state, selected, |sessions| sessions);
+        }
+
+        SessionListMsg::KillFinished => {
+            let finished = finished_sessions(state, stories);
+            ask(state, finished, |sessions| sessions);
+        }
+
+        SessionListMsg::ConfirmKill => {
+            if let Some(sessions) = cleanup_list::confirm(state) {
+                return vec![Cmd::KillSessions {
+                    names: sessions.into_iter().map(|s| s.name).collect(),
+                }];
+            }
+        }
+
+        SessionListMsg::CancelKill => cleanup_list::cancel(state),
+    }
+
+    vec![Cmd::None]
+}
+
+pub fn key_to_msg(key: KeyEvent, state: &SessionListState) -> Option<SessionListMsg> {
+    if state.pending.is_some() {
+        return confirm_key_to_msg(key, SessionListMsg::ConfirmKill, SessionListMsg::CancelKill);
+    }
+
+    match key.code {
+        navkey!(down) => Some(SessionListMsg::FocusNext),
+        navkey!(up) => Some(SessionListMsg::FocusPrev),
+        KeyCode::Enter => Some(SessionListMsg::SwitchTo),
+        KeyCode::Char('r') => Some(SessionListMsg::Refresh),
+        KeyCode::Char('D') => Some(SessionListMsg::KillSelected),
+        KeyCode::Char('X') => Some(SessionListMsg::KillFinished),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dummy;
+
+    fn session(name: &str, story_id: Option<i32>) -> SessionInfo {
+        SessionInfo {
+            name: name.to_string(),
+            attached: false,
+            windows: Some(1),
+            story_id,
+        }
+    }
+
+    #[test]
+    fn kills_sessions_of_finished_stories_by_id_or_name() {
+        let mut stories = dummy::stories();
+        stories[0].completed = true;
+        stories[1].completed = true;
+        stories[2].completed = false;
+
+        let mut state = SessionListState::default();
+        set_sessions(
+            &mut state,
+            vec![
+                // renamed since the session was created
+                session("scn--old-name", Some(stories[0].id)),
+                session(&Story::tmux_session_name(&stories[1].name), None),
+                session("scn--unfinished", Some(stories[2].id)),
+            ],
+        );
+
+        update(&mut state, &stories, SessionListMsg::KillFinished);
+        let cmds = update(&mut state, &stories, SessionListMsg::ConfirmKill);
+
+        let mut expected = vec![
+            Story::tmux_session_name(&stories[1].name),
+            "scn--old-name".to_string(),
+        ];
+        expected.sort();
+        assert!(matches!(
+            cmds.as_slice(),
+            [Cmd::KillSessions { names }] if *names == expected
+        ));
+        assert!(state.pending.is_none());
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    api::story::Story,
+    app::{cmd::Cmd, model::StandupModalState, msg::StandupModalMsg},
+    note::standup::build_standup,
+};
+
+pub fn update(
+    state: &mut StandupModalState,
+    stories: &[Story],
+    notes_dir: &Path,
+    msg: StandupModalMsg,
+) -> Vec<Cmd> {
+    match msg {
+        StandupModalMsg::Open => {
+            state.text = build_standup(stories, notes_dir, crate::time::today()).to_text();
+            state.copied = false;
+            state.is_showing = true;
+            vec![Cmd::None]
+        }
+
+        StandupModalMsg::Close => {
+            state.is_showing = false;
+            vec![Cmd::None]
+        }
+
+        StandupModalMsg::Copy => vec![Cmd::CopyToClipboard {
+            text: state.text.clone(),
+        }],
+    }
+}
+
+pub fn key_to_msg(key: KeyEvent) -> Option<StandupModalMsg> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => Some(StandupModalMsg::Close),
+        KeyCode::Char('y') | KeyCode::Enter => Some(StandupModalMsg::Copy),
+        _ => None,
+    }
+}
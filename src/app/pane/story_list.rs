@@ -198,8 +198,8 @@ pub fn update(
 
         StoryListMsg::TmuxEnter => {
             if let Some(story) = get_hovered_story(state, stories) {
-                vec![Cmd::OpenTmuxSession {
-                    story_name: story.name.clone(),
+                vec![Cmd::OpenSession {
+                    story: story.clone(),
                 }]
             } else {
                 vec![Cmd::None]
@@ -261,7 +261,7 @@ pub fn key_to_msg(key: KeyEvent) -> Option<StoryListMsg> {
         Key::OpenNote => Some(StoryListMsg::OpenNote),
         Key::SelectStory => Some(StoryListMsg::SelectStory),
         Key::EditDescription => Some(StoryListMsg::EditStoryContents),
-        Key::Tmux => Some(StoryListMsg::TmuxEnter),
+        Key::Session => Some(StoryListMsg::TmuxEnter),
         _ => None,
     }
 }
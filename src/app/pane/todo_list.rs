@@ -0,0 +1,129 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    app::{cmd::Cmd, msg::TodoListMsg},
+    navkey,
+    note::todo::{Todo, TodoGroup},
+};
+
+pub use crate::app::model::TodoListState;
+
+/// Returns the todos that should be shown, in display order, alongside their note.
+pub fn visible_todos(state: &TodoListState) -> Vec<(&TodoGroup, &Todo)> {
+    state
+        .groups
+        .iter()
+        .flat_map(|group| {
+            group
+                .todos
+                .iter()
+                .filter(|todo| state.show_done || !todo.checked)
+                .map(move |todo| (group, todo))
+        })
+        .collect()
+}
+
+fn is_selected(state: &TodoListState, group: &TodoGroup, todo: &Todo) -> bool {
+    state
+        .selected
+        .as_ref()
+        .is_some_and(|(path, line)| *path == group.path && *line == todo.line)
+}
+
+fn selected_index(state: &TodoListState, visible: &[(&TodoGroup, &Todo)]) -> Option<usize> {
+    visible
+        .iter()
+        .position(|(group, todo)| is_selected(state, group, todo))
+}
+
+/// Replaces the todos with a fresh scan, keeping the selection on the same checkbox if
+/// it still exists.
+pub fn set_groups(state: &mut TodoListState, groups: Vec<TodoGroup>) {
+    state.groups = groups;
+
+    let visible = visible_todos(state);
+    if selected_index(state, &visible).is_none() {
+        state.selected = visible
+            .first()
+            .map(|(group, todo)| (group.path.clone(), todo.line));
+    }
+}
+
+pub fn update(state: &mut TodoListState, msg: TodoListMsg) -> Vec<Cmd> {
+    match msg {
+        TodoListMsg::FocusNext => {
+            let visible = visible_todos(state);
+            if visible.is_empty() {
+                return vec![Cmd::None];
+            }
+
+            let next_idx = match selected_index(state, &visible) {
+                Some(idx) => (idx + 1) % visible.len(),
+                None => 0,
+            };
+
+            let (group, todo) = visible[next_idx];
+            state.selected = Some((group.path.clone(), todo.line));
+            vec![Cmd::None]
+        }
+
+        TodoListMsg::FocusPrev => {
+            let visible = visible_todos(state);
+            if visible.is_empty() {
+                return vec![Cmd::None];
+            }
+
+            let prev_idx = match selected_index(state, &visible) {
+                Some(0) | None => visible.len() - 1,
+                Some(idx) => idx - 1,
+            };
+
+            let (group, todo) = visible[prev_idx];
+            state.selected = Some((group.path.clone(), todo.line));
+            vec![Cmd::None]
+        }
+
+        TodoListMsg::Toggle => {
+            let visible = visible_todos(state);
+            match selected_index(state, &visible).map(|idx| visible[idx]) {
+                Some((group, todo)) => vec![Cmd::ToggleTodo {
+                    path: group.path.clone(),
+                    line: todo.line,
+                    text: todo.text.clone(),
+                }],
+                None => vec![Cmd::None],
+            }
+        }
+
+        TodoListMsg::OpenAtLine => match &state.selected {
+            Some((path, line)) => vec![Cmd::OpenNoteAtLine {
+                path: path.clone(),
+                line: *line,
+            }],
+            None => vec![Cmd::None],
+        },
+
+        TodoListMsg::ToggleShowDone => {
+            state.show_done = !state.show_done;
+
+            let visible = visible_todos(state);
+            if selected_index(state, &visible).is_none() {
+                state.selected = visible
+                    .first()
+                    .map(|(group, todo)| (group.path.clone(), todo.line));
+            }
+            vec![Cmd::None]
+        }
+    }
+}
+
+pub fn key_to_msg(key: KeyEvent) -> Option<TodoListMsg> {
+    match key.code {
+        navkey!(down) => Some(TodoListMsg::FocusNext),
+        navkey!(up) => Some(TodoListMsg::FocusPrev),
+        KeyCode::Enter => Some(TodoListMsg::OpenAtLine),
+        KeyCode::Char(' ') | KeyCode::Char('x') => Some(TodoListMsg::Toggle),
+        KeyCode::Char('f') => Some(TodoListMsg::ToggleShowDone),
+        _ => None,
+    }
+}
@@ -0,0 +1,147 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    api::story::Story,
+    app::{
+        cmd::Cmd,
+        model::{WorktreeListState, WorktreeRemoval},
+        msg::WorktreeListMsg,
+        pane::cleanup_list::{self, ask, confirm_key_to_msg},
+    },
+    navkey,
+    worktree::WorktreeInfo,
+};
+
+/// The story a worktree's branch refers to, if it is one we have loaded.
+pub fn worktree_story<'a>(worktree: &WorktreeInfo, stories: &'a [Story]) -> Option<&'a Story> {
+    let id = worktree.story_id?;
+    stories.iter().find(|s| s.id == id)
+}
+
+/// Worktrees whose story has been completed.
+pub fn finished_worktrees(state: &WorktreeListState, stories: &[Story]) -> Vec<WorktreeInfo> {
+    state
+        .items
+        .iter()
+        .filter(|wt| worktree_story(wt, stories).is_some_and(|s| s.completed))
+        .cloned()
+        .collect()
+}
+
+/// Lists the worktrees by repo, then path.
+pub fn set_worktrees(state: &mut WorktreeListState, mut worktrees: Vec<WorktreeInfo>) {
+    worktrees.sort_by(|a, b| a.repo.cmp(&b.repo).then_with(|| a.path.cmp(&b.path)));
+    cleanup_list::set_items(state, worktrees);
+}
+
+fn removal(worktrees: Vec<WorktreeInfo>) -> WorktreeRemoval {
+    WorktreeRemoval {
+        worktrees,
+        delete_branches: false,
+    }
+}
+
+pub fn update(state: &mut WorktreeListState, stories: &[Story], msg: WorktreeListMsg) -> Vec<Cmd> {
+    match msg {
+        WorktreeListMsg::FocusNext => cleanup_list::focus_next(state),
+        WorktreeListMsg::FocusPrev => cleanup_list::focus_prev(state),
+        WorktreeListMsg::Refresh => return vec![Cmd::LoadWorktrees],
+
+        WorktreeListMsg::RemoveSelected => {
+            let selected = cleanup_list::selected(state);
+            ask(state, selected, removal);
+        }
+
+        WorktreeListMsg::RemoveFinished => {
+            let finished = finished_worktrees(state, stories);
+            ask(state, finished, removal);
+        }
+
+        WorktreeListMsg::ToggleDeleteBranches => {
+            if let Some(removal) = &mut state.pending {
+                removal.delete_branches = !removal.delete_branches;
+            }
+        }
+
+        WorktreeListMsg::ConfirmRemoval => {
+            if let Some(removal) = cleanup_list::confirm(state) {
+                return vec![Cmd::RemoveWorktrees {
+                    worktrees: removal.worktrees,
+                    delete_branches: removal.delete_branches,
+                }];
+            }
+        }
+
+        WorktreeListMsg::CancelRemoval => cleanup_list::cancel(state),
+    }
+
+    vec![Cmd::None]
+}
+
+pub fn key_to_msg(key: KeyEvent, state: &WorktreeListState) -> Option<WorktreeListMsg> {
+    if state.pending.is_some() {
+        return match key.code {
+            KeyCode::Char('b') => Some(WorktreeListMsg::ToggleDeleteBranches),
+            _ => confirm_key_to_msg(
+                key,
+                WorktreeListMsg::ConfirmRemoval,
+                WorktreeListMsg::CancelRemoval,
+            ),
+        };
+    }
+
+    match key.code {
+        navkey!(down) => Some(WorktreeListMsg::FocusNext),
+        navkey!(up) => Some(WorktreeListMsg::FocusPrev),
+        KeyCode::Char('r') => Some(WorktreeListMsg::Refresh),
+        KeyCode::Char('D') => Some(WorktreeListMsg::RemoveSelected),
+        KeyCode::Char('X') => Some(WorktreeListMsg::RemoveFinished),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::{dummy, worktree::GitStatus};
+
+    fn worktree(story_id: Option<i32>) -> WorktreeInfo {
+        WorktreeInfo {
+            repo: "api".to_string(),
+            path: PathBuf::from(format!("/r/api/.worktrees/{:?}", story_id)),
+            branch: story_id.map(|id| format!("jane/sc-{}/x", id)),
+            story_id,
+            status: GitStatus::default(),
+        }
+    }
+
+    #[test]
+    fn removes_worktrees_of_finished_stories_after_confirming() {
+        let mut stories = dummy::stories();
+        stories[0].completed = true;
+        stories[1].completed = false;
+
+        let mut state = WorktreeListState::default();
+        set_worktrees(
+            &mut state,
+            vec![
+                worktree(Some(stories[0].id)),
+                worktree(Some(stories[1].id)),
+                worktree(None),
+            ],
+        );
+
+        update(&mut state, &stories, WorktreeListMsg::RemoveFinished);
+        update(&mut state, &stories, WorktreeListMsg::ToggleDeleteBranches);
+        let cmds = update(&mut state, &stories, WorktreeListMsg::ConfirmRemoval);
+
+        assert!(matches!(
+            cmds.as_slice(),
+            [Cmd::RemoveWorktrees { worktrees, delete_branches: true }]
+                if worktrees.len() == 1 && worktrees[0].story_id == Some(stories[0].id)
+        ));
+        assert!(state.pending.is_none());
+    }
+}
@@ -5,13 +5,21 @@ use crate::{
     app::{
         App,
         cmd::Cmd,
-        model::{LoadingState, ViewType},
-        msg::{CreateNoteModalMsg, EpicListMsg, IterationListMsg, Msg},
-        pane::{action_menu, create_note_modal, description_modal, epic_list, iteration_list, notes_list, story_list},
+        model::{LoadingState, NoteAction, ViewType},
+        msg::{
+            CaptureModalMsg, CreateNoteModalMsg, EpicListMsg, IterationListMsg, Msg,
+            NoteActionModalMsg, NoteSearchMsg, StandupModalMsg,
+        },
+        pane::{
+            action_menu, capture_modal, create_note_modal, description_modal, epic_list,
+            iteration_list, note_action_modal, note_search, notes_list, repo_picker, session_list,
+            standup_modal, story_list, todo_list, worktree_list,
+        },
     },
     dbg_file,
     error::ErrorInfo,
     keybindings::Key,
+    note::{daily::DAILY_DIR, links::LinkGraph, sync::SyncStatus, tags::missing_story_labels},
 };
 
 impl App {
@@ -24,6 +32,10 @@ impl App {
         match msg {
             Msg::Quit => {
                 self.exit = true;
+                // the undo window for a deleted note closes with the app
+                if let Some(deleted) = self.model.ui.notes_list.deleted.take() {
+                    deleted.purge().ok();
+                }
                 for handle in &self.model.data.async_handles {
                     if !handle.is_finished() {
                         handle.abort();
@@ -43,9 +55,57 @@ impl App {
 
             Msg::NotesList(notes_msg) => notes_list::update(
                 &mut self.model.ui.notes_list,
+                &self.model.data.link_graph,
                 notes_msg,
             ),
 
+            Msg::TodoList(todo_msg) => todo_list::update(&mut self.model.ui.todo_list, todo_msg),
+            Msg::SessionList(session_msg) => session_list::update(
+                &mut self.model.ui.session_list,
+                &self.model.data.stories,
+                session_msg,
+            ),
+
+            Msg::WorktreeList(worktree_msg) => worktree_list::update(
+                &mut self.model.ui.worktree_list,
+                &self.model.data.stories,
+                worktree_msg,
+            ),
+
+            Msg::NoteSearch(msg) => note_search::update(
+                &mut self.model.ui.note_search,
+                &self.model.data.search_index,
+                msg,
+            ),
+
+            Msg::SearchIndexLoaded(index) => {
+                self.model.data.link_graph =
+                    LinkGraph::build(&index, &self.model.config.notes_dir);
+                self.model.data.search_index = index;
+                self.model.ui.note_search.indexing = false;
+                note_search::rerun(&mut self.model.ui.note_search, &self.model.data.search_index);
+                vec![self.mirror_story_labels()]
+            }
+
+            Msg::TodosLoaded(groups) => {
+                todo_list::set_groups(&mut self.model.ui.todo_list, groups);
+                vec![Cmd::None]
+            }
+
+            Msg::NotesChanged(change) => {
+                notes_list::apply_change(&mut self.model.ui.notes_list, &change);
+
+                // the initial scan has no changed paths and the index is refreshed on startup
+                if change.paths.is_empty() {
+                    return vec![Cmd::None];
+                }
+
+                if self.model.ui.active_view == ViewType::Todos {
+                    return vec![Cmd::RefreshTodos, Cmd::RefreshSearchIndex];
+                }
+                vec![Cmd::RefreshSearchIndex]
+            }
+
             Msg::IterationList(msg) => {
                 let current = self.model.data.current_iterations.as_deref().unwrap_or(&[]);
                 let all = &self.model.data.iterations;
@@ -66,8 +126,9 @@ impl App {
                     self.model.ui.loading = LoadingState::Loaded;
                 }
 
-                // Select first story if none selected and list is non-empty
-                if self.model.ui.story_list.selected_story_id.is_none() && !stories.is_empty() {
+                // Select first story if none selected, or the preselected story isn't ours
+                let selected = self.model.ui.story_list.selected_story_id;
+                if selected.is_none_or(|id| !stories.iter().any(|s| s.id == id)) {
                     self.model.ui.story_list.selected_story_id = stories.first().map(|s| s.id);
                 }
 
@@ -81,7 +142,11 @@ impl App {
                         .zip(stories.iter())
                         .all(|(a, b)| a.id == b.id)
                 {
-                    return vec![Cmd::None];
+                    // same stories, but their state, comments and labels, which the
+                    // standup and note tags are built from, may have changed
+                    self.model.data.stories = stories.clone();
+                    self.model.cache.iteration_stories = Some(stories);
+                    return vec![Cmd::WriteCache, self.mirror_story_labels()];
                 }
 
                 self.model.data.stories = stories.clone();
@@ -144,7 +209,7 @@ impl App {
 
                 self.model.cache.iteration_stories = Some(stories);
 
-                vec![Cmd::WriteCache]
+                vec![Cmd::WriteCache, self.mirror_story_labels()]
             }
 
             Msg::EpicsLoaded(mut epics) => {
@@ -201,39 +266,37 @@ impl App {
 
             Msg::SwitchToView(view_type) => {
                 self.model.ui.active_view = view_type;
-                if view_type == ViewType::Notes {
-                    let (daily, stories, iterations, epics, scratch) =
-                        notes_list::scan_notes(&self.model.config.notes_dir);
-                    self.model.ui.notes_list.daily_notes = daily;
-                    self.model.ui.notes_list.story_notes = stories;
-                    self.model.ui.notes_list.iteration_notes = iterations;
-                    self.model.ui.notes_list.epic_notes = epics;
-                    self.model.ui.notes_list.scratch_notes = scratch;
-                    if self.model.ui.notes_list.selected_path.is_none() {
-                        let nl = &self.model.ui.notes_list;
-                        let first = nl.daily_notes.first()
-                            .or_else(|| nl.story_notes.first())
-                            .or_else(|| nl.iteration_notes.first())
-                            .or_else(|| nl.epic_notes.first())
-                            .or_else(|| nl.scratch_notes.first())
-                            .cloned();
-                        self.model.ui.notes_list.selected_path = first;
-                    }
+                // the watcher keeps the list current, only scan here if it isn't running
+                if view_type == ViewType::Notes && self.model.data.notes_watcher.is_none() {
+                    notes_list::rescan(&mut self.model.ui.notes_list, &self.model.config.notes_dir);
+                }
+                if view_type == ViewType::Todos {
+                    return vec![Cmd::RefreshTodos];
+                }
+                if view_type == ViewType::Worktrees {
+                    return vec![Cmd::LoadWorktrees];
+                }
+                if view_type == ViewType::Sessions {
+                    return vec![Cmd::LoadSessions];
+                }
+                if view_type == ViewType::Search {
+                    self.model.ui.note_search.indexing = true;
+                    return vec![Cmd::RefreshSearchIndex];
                 }
                 vec![Cmd::None]
             }
 
             Msg::NoteOpened => {
-                if self.model.ui.active_view == ViewType::Notes {
-                    let (daily, stories, iterations, epics, scratch) =
-                        notes_list::scan_notes(&self.model.config.notes_dir);
-                    self.model.ui.notes_list.daily_notes = daily;
-                    self.model.ui.notes_list.story_notes = stories;
-                    self.model.ui.notes_list.iteration_notes = iterations;
-                    self.model.ui.notes_list.epic_notes = epics;
-                    self.model.ui.notes_list.scratch_notes = scratch;
+                if self.model.ui.active_view == ViewType::Notes
+                    && self.model.data.notes_watcher.is_none()
+                {
+                    notes_list::rescan(&mut self.model.ui.notes_list, &self.model.config.notes_dir);
                 }
-                vec![Cmd::None]
+                // the edit may have changed links, so always re-index
+                if self.model.ui.active_view == ViewType::Todos {
+                    return vec![Cmd::RefreshTodos, Cmd::RefreshSearchIndex];
+                }
+                vec![Cmd::RefreshSearchIndex]
             }
 
             Msg::CacheWritten => vec![Cmd::None],
@@ -257,6 +320,8 @@ impl App {
                     action_menu::update(
                         &mut self.model.ui,
                         &self.model.data,
+                        &self.model.config,
+                        self.model.cache.mention_name.as_deref(),
                         menu_msg,
                         hovered_story,
                     )
@@ -282,6 +347,90 @@ impl App {
                 description_modal::update(&mut self.model.ui.description_modal, modal_msg)
             }
 
+            Msg::NoteActionModal(modal_msg) => note_action_modal::update(
+                &mut self.model.ui.note_action_modal,
+                &mut self.model.ui.notes_list,
+                &self.model.data,
+                &self.model.config,
+                modal_msg,
+            ),
+
+            Msg::StandupModal(modal_msg) => standup_modal::update(
+                &mut self.model.ui.standup_modal,
+                &self.model.data.stories,
+                &self.model.config.notes_dir,
+                modal_msg,
+            ),
+
+            Msg::CopiedToClipboard => {
+                if self.model.ui.standup_modal.is_showing {
+                    self.model.ui.standup_modal.copied = true;
+                }
+                vec![Cmd::None]
+            }
+
+            Msg::IterationReportWritten(path) => vec![Cmd::OpenNoteAtLine { path, line: 0 }],
+
+            Msg::NoteDeleteExpired(trashed) => {
+                let notes_list = &mut self.model.ui.notes_list;
+                if let Some(deleted) = notes_list.deleted.take_if(|d| d.trashed == trashed)
+                    && let Err(e) = deleted.purge()
+                {
+                    self.model.ui.errors.push(ErrorInfo::new("Failed to delete note", e));
+                }
+                vec![Cmd::None]
+            }
+
+            Msg::CaptureModal(modal_msg) => {
+                capture_modal::update(&mut self.model.ui.capture_modal, modal_msg)
+            }
+
+            Msg::RepoPicker(picker_msg) => {
+                repo_picker::update(&mut self.model.ui.repo_picker, picker_msg)
+            }
+
+            Msg::ReposLoaded(repos) => {
+                repo_picker::set_repos(&mut self.model.ui.repo_picker, repos);
+                vec![Cmd::None]
+            }
+
+            Msg::WorktreesLoaded(worktrees) => {
+                worktree_list::set_worktrees(&mut self.model.ui.worktree_list, worktrees);
+                vec![Cmd::None]
+            }
+
+            Msg::WorktreesRemoved => vec![Cmd::LoadWorktrees],
+
+            Msg::NotesSynced(status) => {
+                self.model.ui.sync_conflicts = match status {
+                    SyncStatus::Synced => None,
+                    SyncStatus::Conflicts(files) => {
+                        self.model.ui.errors.push(ErrorInfo::new(
+                            "Notes sync conflict",
+                            format!(
+                                "Rebasing onto the remote conflicts in: {}. Resolve it with \
+                                 git in the notes directory",
+                                files.join(", ")
+                            ),
+                        ));
+                        Some(files)
+                    }
+                };
+                vec![Cmd::None]
+            }
+
+            Msg::StoryStatusesLoaded(statuses) => {
+                self.model.ui.story_list.git_statuses = statuses;
+                vec![Cmd::None]
+            }
+
+            Msg::SessionsLoaded(sessions) => {
+                session_list::set_sessions(&mut self.model.ui.session_list, sessions);
+                vec![Cmd::None]
+            }
+
+            Msg::SessionsKilled => vec![Cmd::LoadSessions],
+
             Msg::CreateNoteModal(modal_msg) => create_note_modal::update(
                 &mut self.model.ui.create_note_modal,
                 &self.model.config,
@@ -290,7 +439,22 @@ impl App {
         }
     }
 
-    /// Intercepts keys for search state in Iteration/Epic views.
+    /// Adds story labels to story note tags when `mirror_story_labels` is enabled and
+    /// some note is missing one.
+    fn mirror_story_labels(&self) -> Cmd {
+        if !self.model.config.mirror_story_labels {
+            return Cmd::None;
+        }
+
+        let notes = missing_story_labels(&self.model.data.search_index, &self.model.data.stories);
+        if notes.is_empty() {
+            Cmd::None
+        } else {
+            Cmd::MirrorStoryLabels { notes }
+        }
+    }
+
+    /// Intercepts keys for search state in Iteration/Epic/Search views.
     ///
     /// Two modes:
     /// - **Active** (`search_active = true`): typing mode. j/k are consumed (not
@@ -307,6 +471,10 @@ impl App {
                 self.model.ui.epic_list.search_active,
                 !self.model.ui.epic_list.search_query.is_empty(),
             ),
+            ViewType::Search => (
+                self.model.ui.note_search.search_active,
+                !self.model.ui.note_search.search_query.is_empty(),
+            ),
             _ => return None,
         };
 
@@ -318,16 +486,19 @@ impl App {
                 KeyCode::Esc => match self.model.ui.active_view {
                     ViewType::Iterations => Msg::IterationList(IterationListMsg::DeactivateSearch),
                     ViewType::Epics => Msg::EpicList(EpicListMsg::DeactivateSearch),
+                    ViewType::Search => Msg::NoteSearch(NoteSearchMsg::DeactivateSearch),
                     _ => unreachable!(),
                 },
                 KeyCode::Backspace => match self.model.ui.active_view {
                     ViewType::Iterations => Msg::IterationList(IterationListMsg::SearchBackspace),
                     ViewType::Epics => Msg::EpicList(EpicListMsg::SearchBackspace),
+                    ViewType::Search => Msg::NoteSearch(NoteSearchMsg::SearchBackspace),
                     _ => unreachable!(),
                 },
                 KeyCode::Char(c) => match self.model.ui.active_view {
                     ViewType::Iterations => Msg::IterationList(IterationListMsg::SearchInput(c)),
                     ViewType::Epics => Msg::EpicList(EpicListMsg::SearchInput(c)),
+                    ViewType::Search => Msg::NoteSearch(NoteSearchMsg::SearchInput(c)),
                     _ => unreachable!(),
                 },
                 _ => return Some(vec![Cmd::None]),
@@ -340,6 +511,7 @@ impl App {
             let msg = match self.model.ui.active_view {
                 ViewType::Iterations => Msg::IterationList(IterationListMsg::ClearSearch),
                 ViewType::Epics => Msg::EpicList(EpicListMsg::ClearSearch),
+                ViewType::Search => Msg::NoteSearch(NoteSearchMsg::ClearSearch),
                 _ => unreachable!(),
             };
             return Some(self.update(msg));
@@ -375,6 +547,49 @@ impl App {
             };
         }
 
+        if self.model.ui.standup_modal.is_showing {
+            return if let Some(modal_msg) = standup_modal::key_to_msg(key) {
+                self.update(Msg::StandupModal(modal_msg))
+            } else {
+                vec![Cmd::None]
+            };
+        }
+
+        // Worktree removal confirmation, so y/n/b aren't taken by global keys
+        if self.model.ui.worktree_list.pending.is_some() {
+            return if let Some(msg) = worktree_list::key_to_msg(key, &self.model.ui.worktree_list) {
+                self.update(Msg::WorktreeList(msg))
+            } else {
+                vec![Cmd::None]
+            };
+        }
+
+        // Session kill confirmation, likewise
+        if self.model.ui.session_list.pending.is_some() {
+            return if let Some(msg) = session_list::key_to_msg(key, &self.model.ui.session_list) {
+                self.update(Msg::SessionList(msg))
+            } else {
+                vec![Cmd::None]
+            };
+        }
+
+        if self.model.ui.repo_picker.is_showing {
+            return if let Some(picker_msg) = repo_picker::key_to_msg(key) {
+                self.update(Msg::RepoPicker(picker_msg))
+            } else {
+                vec![Cmd::None]
+            };
+        }
+
+        // Quick capture intercepts all keys when showing
+        if self.model.ui.capture_modal.is_showing {
+            return if let Some(modal_msg) = capture_modal::key_to_msg(key) {
+                self.update(Msg::CaptureModal(modal_msg))
+            } else {
+                vec![Cmd::None]
+            };
+        }
+
         // Create note modal intercepts all keys when showing
         if self.model.ui.create_note_modal.is_showing {
             return if let Some(modal_msg) = create_note_modal::key_to_msg(key) {
@@ -384,6 +599,17 @@ impl App {
             };
         }
 
+        // Note rename/move/confirm prompts intercept all keys when showing
+        if self.model.ui.note_action_modal.is_showing {
+            return if let Some(modal_msg) =
+                note_action_modal::key_to_msg(&self.model.ui.note_action_modal, key)
+            {
+                self.update(Msg::NoteActionModal(modal_msg))
+            } else {
+                vec![Cmd::None]
+            };
+        }
+
         // Search bar intercepts most keys when active in Iteration/Epic views
         if let Some(cmds) = self.try_handle_search_key(key) {
             return cmds;
@@ -406,9 +632,12 @@ impl App {
                 }
                 Key::DailyNote => {
                     let today = crate::time::today();
-                    let path = self.config.notes_dir.join("daily").join(format!("{}.md", today));
+                    let path = self.config.notes_dir.join(DAILY_DIR).join(format!("{}.md", today));
                     return vec![Cmd::OpenDailyNote { path }];
                 }
+                Key::Capture => return self.update(Msg::CaptureModal(CaptureModalMsg::Open)),
+                Key::Standup => return self.update(Msg::StandupModal(StandupModalMsg::Open)),
+                Key::Timer => return vec![Cmd::ToggleTimer],
                 _ => {}
             }
         }
@@ -496,15 +725,50 @@ impl App {
                     return self.update(Msg::StoryList(msg));
                 }
             }
+            ViewType::Search => {
+                if key.code == KeyCode::Char('/') {
+                    return self.update(Msg::NoteSearch(NoteSearchMsg::ActivateSearch));
+                }
+                if let Some(msg) = note_search::key_to_msg(key) {
+                    return self.update(Msg::NoteSearch(msg));
+                }
+            }
+            ViewType::Todos => {
+                if let Some(msg) = todo_list::key_to_msg(key) {
+                    return self.update(Msg::TodoList(msg));
+                }
+            }
+            ViewType::Worktrees => {
+                if let Some(msg) = worktree_list::key_to_msg(key, &self.model.ui.worktree_list) {
+                    return self.update(Msg::WorktreeList(msg));
+                }
+            }
+            ViewType::Sessions => {
+                if let Some(msg) = session_list::key_to_msg(key, &self.model.ui.session_list) {
+                    return self.update(Msg::SessionList(msg));
+                }
+            }
             ViewType::Notes => {
                 if key.code == KeyCode::Char('n') {
                     return self.update(Msg::CreateNoteModal(CreateNoteModalMsg::Open));
                 }
+                let action = match key.code {
+                    KeyCode::Char('r') => Some(NoteAction::Rename),
+                    KeyCode::Char('D') => Some(NoteAction::Delete),
+                    KeyCode::Char('m') => Some(NoteAction::Move),
+                    KeyCode::Char('a') => Some(NoteAction::Archive),
+                    KeyCode::Char('A') => Some(NoteAction::ArchiveFinished),
+                    KeyCode::Char('t') => Some(NoteAction::EditTags),
+                    KeyCode::Char('#') => Some(NoteAction::FilterTags),
+                    _ => None,
+                };
+                if let Some(action) = action {
+                    return self.update(Msg::NoteActionModal(NoteActionModalMsg::Open(action)));
+                }
                 if let Some(msg) = notes_list::key_to_msg(key) {
                     return self.update(Msg::NotesList(msg));
                 }
             }
-            _ => {}
         }
 
         vec![Cmd::None]
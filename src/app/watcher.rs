@@ -0,0 +1,172 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::{
+    sync::mpsc::{self, UnboundedSender},
+    task::JoinHandle,
+};
+
+use crate::{
+    app::{
+        model::NoteInfo,
+        msg::Msg,
+        pane::notes_list::{note_info, scan_subdir},
+    },
+    dbg_file,
+    note::manage::CATEGORIES,
+};
+
+/// Editors write files in several steps (temp file, rename, chmod), so events are
+/// collected for this long before rescanning.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Fresh listings for the note categories touched by a batch of filesystem events.
+#[derive(Debug, Clone, Default)]
+pub struct NotesChange {
+    /// `(category, notes)` for every rescanned category
+    pub categories: Vec<(&'static str, Vec<PathBuf>)>,
+    /// Details of every note in the rescanned categories
+    pub info: Vec<(PathBuf, NoteInfo)>,
+    /// Notes that were created, modified or removed
+    pub paths: Vec<PathBuf>,
+}
+
+impl NotesChange {
+    /// Rescans only the categories that `paths` belong to.
+    fn scan(notes_dir: &Path, paths: Vec<PathBuf>) -> NotesChange {
+        let categories: Vec<_> = CATEGORIES
+            .iter()
+            .filter(|category| {
+                let dir = notes_dir.join(category);
+                paths.iter().any(|p| p.starts_with(&dir))
+            })
+            .map(|category| (*category, scan_subdir(notes_dir, category)))
+            .collect();
+
+        let info = categories
+            .iter()
+            .flat_map(|(_, notes)| notes)
+            .map(|path| (path.clone(), note_info(path)))
+            .collect();
+
+        NotesChange {
+            categories,
+            info,
+            paths,
+        }
+    }
+
+    /// A full scan of every category, used to populate the list on startup.
+    fn scan_all(notes_dir: &Path) -> NotesChange {
+        let dirs = CATEGORIES.iter().map(|c| notes_dir.join(c)).collect();
+        NotesChange {
+            paths: Vec::new(),
+            ..NotesChange::scan(notes_dir, dirs)
+        }
+    }
+}
+
+/// Whether an event for `path` can affect the notes list. Hidden files and folders
+/// (`.git`, `.obsidian`, editor swap files) are ignored.
+fn is_relevant(notes_dir: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(notes_dir) else {
+        return false;
+    };
+
+    let hidden = relative
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    if hidden {
+        return false;
+    }
+
+    // directories have no extension, and removing a category folder matters too
+    matches!(path.extension().and_then(|e| e.to_str()), Some("md") | None)
+}
+
+/// Watches `notes_dir` recursively and sends `Msg::NotesChanged` with rescanned
+/// categories whenever notes change on disk. The returned watcher must be kept alive
+/// for events to keep coming.
+pub fn spawn_notes_watcher(
+    notes_dir: PathBuf,
+    sender: UnboundedSender<Msg>,
+) -> anyhow::Result<(RecommendedWatcher, JoinHandle<()>)> {
+    let (event_sender, mut events) = mpsc::unbounded_channel::<Vec<PathBuf>>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        if let Ok(event) = res {
+            event_sender.send(event.paths).ok();
+        }
+    })?;
+    watcher.watch(&notes_dir, RecursiveMode::Recursive)?;
+
+    let handle = tokio::spawn(async move {
+        let dir = notes_dir.clone();
+        if let Ok(initial) = tokio::task::spawn_blocking(move || NotesChange::scan_all(&dir)).await
+        {
+            sender.send(Msg::NotesChanged(initial)).ok();
+        }
+
+        while let Some(paths) = events.recv().await {
+            let mut changed: HashSet<PathBuf> = paths.into_iter().collect();
+
+            // collect the rest of the burst before rescanning
+            loop {
+                match tokio::time::timeout(DEBOUNCE, events.recv()).await {
+                    Ok(Some(paths)) => changed.extend(paths),
+                    Ok(None) => return,
+                    Err(_) => break,
+                }
+            }
+
+            let changed: Vec<PathBuf> = changed
+                .into_iter()
+                .filter(|p| is_relevant(&notes_dir, p))
+                .collect();
+            if changed.is_empty() {
+                continue;
+            }
+
+            dbg_file!("Notes changed on disk: {:?}", changed);
+
+            let dir = notes_dir.clone();
+            match tokio::task::spawn_blocking(move || NotesChange::scan(&dir, changed)).await {
+                Ok(change) => {
+                    if sender.send(Msg::NotesChanged(change)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => dbg_file!("Notes rescan failed: {}", e),
+            }
+        }
+    });
+
+    Ok((watcher, handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_touched_categories_are_rescanned() {
+        let notes_dir = Path::new("/notes");
+        assert!(is_relevant(notes_dir, Path::new("/notes/scratch/idea.md")));
+        assert!(!is_relevant(
+            notes_dir,
+            Path::new("/notes/.obsidian/workspace.json")
+        ));
+        assert!(!is_relevant(
+            notes_dir,
+            Path::new("/notes/scratch/.idea.md.swp")
+        ));
+
+        let change = NotesChange::scan(notes_dir, vec![PathBuf::from("/notes/scratch/idea.md")]);
+        let categories: Vec<_> = change.categories.iter().map(|(c, _)| *c).collect();
+        assert_eq!(categories, vec!["scratch"]);
+    }
+}
@@ -0,0 +1,96 @@
+use slugify::slugify;
+
+use crate::api::story::Story;
+
+/// Shortcut's own branch convention, which its VCS integration links back to the story.
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{user}/sc-{id}/{slug}";
+
+/// Story names can be long, so the slug is cut down to this many characters.
+const MAX_SLUG_LEN: usize = 50;
+
+/// The story name as a branch friendly slug, cut at a word boundary.
+pub fn branch_slug(name: &str) -> String {
+    let slug = slugify!(name);
+    if slug.len() <= MAX_SLUG_LEN {
+        return slug;
+    }
+
+    // include the next char, so a word ending right at the limit is kept
+    match slug[..=MAX_SLUG_LEN].rfind('-') {
+        Some(idx) if idx > 0 => slug[..idx].to_string(),
+        _ => slug[..MAX_SLUG_LEN].to_string(),
+    }
+}
+
+/// Fills in `template` for `story`. Supports `{user}` (the Shortcut mention name),
+/// `{type}`, `{id}` and `{slug}`. Path segments left empty, e.g. by an unknown user,
+/// are dropped.
+pub fn branch_name(template: &str, story: &Story, user: Option<&str>) -> String {
+    let name = template
+        .replace("{user}", &user.map(|u| slugify!(u)).unwrap_or_default())
+        .replace("{type}", &story.story_type)
+        .replace("{id}", &story.id.to_string())
+        .replace("{slug}", &branch_slug(&story.name));
+
+    name.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The story a branch belongs to, from an `sc-1234` part of its name as Shortcut's VCS
+/// integration matches it.
+pub fn story_id_from_branch(branch: &str) -> Option<i32> {
+    let lower = branch.to_lowercase();
+    lower.match_indices("sc-").find_map(|(idx, _)| {
+        let starts_word = lower[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_ascii_alphanumeric());
+        let digits: String = lower[idx + 3..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if starts_word {
+            digits.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dummy;
+
+    #[test]
+    fn fills_in_template() {
+        let mut story = dummy::stories().remove(0);
+        story.id = 1234;
+        story.name =
+            "Fix the login redirect when the session has expired on a mobile device".into();
+        story.story_type = "bug".into();
+
+        assert_eq!(
+            branch_name(DEFAULT_BRANCH_TEMPLATE, &story, Some("jane")),
+            "jane/sc-1234/fix-the-login-redirect-when-the-session-has"
+        );
+        assert_eq!(
+            branch_name(DEFAULT_BRANCH_TEMPLATE, &story, None),
+            "sc-1234/fix-the-login-redirect-when-the-session-has"
+        );
+        assert_eq!(
+            branch_name("{type}/{id}-{slug}", &story, None),
+            "bug/1234-fix-the-login-redirect-when-the-session-has"
+        );
+    }
+
+    #[test]
+    fn finds_story_id_in_branch() {
+        assert_eq!(story_id_from_branch("jane/sc-1234/fix-login"), Some(1234));
+        assert_eq!(story_id_from_branch("feature/SC-77-thing"), Some(77));
+        assert_eq!(story_id_from_branch("misc-1234/fix"), None);
+        assert_eq!(story_id_from_branch("jane/sc-/fix"), None);
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     env,
     path::{Path, PathBuf},
 };
@@ -20,6 +21,15 @@ pub struct Cache {
     pub epics: Vec<EpicSlim>,
     pub active_story: Option<Story>,
     pub user_id: Option<Uuid>,
+    /// Shortcut mention name of the user, used in branch names
+    #[serde(default)]
+    pub mention_name: Option<String>,
+    /// Repo chosen for each story, relative to `repositories_directory`
+    #[serde(default)]
+    pub story_repos: HashMap<i32, String>,
+    /// Repo new stories of an epic default to
+    #[serde(default)]
+    pub epic_repos: HashMap<i32, String>,
     pub cache_dir: PathBuf,
 }
 
@@ -31,6 +41,9 @@ impl Default for Cache {
             iterations: Vec::new(),
             epics: Vec::new(),
             user_id: None,
+            mention_name: None,
+            story_repos: HashMap::new(),
+            epic_repos: HashMap::new(),
             active_story: None,
             cache_dir: Self::default_cache_dir(),
         }
@@ -42,6 +55,24 @@ impl Cache {
         self.current_iterations.as_ref().map(|v| v.iter().collect())
     }
 
+    /// The repo remembered for a story, falling back to its epic's default.
+    pub fn story_repo(&self, story: &Story) -> Option<&str> {
+        self.story_repos
+            .get(&story.id)
+            .or_else(|| story.epic_id.and_then(|id| self.epic_repos.get(&id)))
+            .map(String::as_str)
+    }
+
+    /// Remembers `repo` for the story, and as its epic's default if the epic has none yet.
+    pub fn remember_repo(&mut self, story_id: i32, epic_id: Option<i32>, repo: &str) {
+        self.story_repos.insert(story_id, repo.to_string());
+        if let Some(epic_id) = epic_id {
+            self.epic_repos
+                .entry(epic_id)
+                .or_insert_with(|| repo.to_string());
+        }
+    }
+
     fn default_cache_dir() -> PathBuf {
         let mut base = env::home_dir().expect("Couldn't find home dir");
         base.push(".cache");
@@ -107,3 +138,25 @@ async fn read_file(file: &PathBuf) -> anyhow::Result<String> {
     f.read_to_string(&mut buf).await?;
     Ok(buf)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dummy;
+
+    #[test]
+    fn story_repo_falls_back_to_epic_default() {
+        let mut stories = dummy::stories();
+        stories[0].epic_id = Some(7);
+        stories[1].epic_id = Some(7);
+
+        let mut cache = Cache::default();
+        cache.remember_repo(stories[0].id, stories[0].epic_id, "work/api");
+        assert_eq!(cache.story_repo(&stories[1]), Some("work/api"));
+
+        // overriding a story keeps the epic default
+        cache.remember_repo(stories[1].id, stories[1].epic_id, "work/web");
+        assert_eq!(cache.story_repo(&stories[1]), Some("work/web"));
+        assert_eq!(cache.epic_repos.get(&7).map(String::as_str), Some("work/api"));
+    }
+}
@@ -24,4 +24,86 @@ pub enum Commands {
 
     #[command()]
     Cat,
+
+    /// Append a timestamped line to the active story's note, or today's daily note
+    #[command(alias = "c")]
+    Capture {
+        /// Text to capture
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+    },
+
+    #[command()]
+    Notes {
+        #[command(subcommand)]
+        command: NotesCommands,
+    },
+
+    /// Print a yesterday/today/blockers summary for standup
+    #[command()]
+    Standup,
+
+    #[command()]
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Print the branch name for the active story
+    #[command(alias = "b")]
+    Branch {
+        /// Copy it to the clipboard as well
+        #[arg(long)]
+        copy: bool,
+    },
+
+    #[command()]
+    Timer {
+        #[command(subcommand)]
+        command: TimerCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum NotesCommands {
+    /// Move notes of completed stories and ended iterations into archive/
+    Archive {
+        /// Only list the notes that would be archived
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rewrite the frontmatter of older notes to the current schema
+    Migrate {
+        /// Only show a diff of the frontmatter changes
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReportCommands {
+    /// Summarise an iteration's stories and their notes as markdown
+    Iteration {
+        /// Iteration name or id, or `current`
+        iteration: String,
+        /// Print the report instead of writing it to iterations/
+        #[arg(long)]
+        stdout: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TimerCommands {
+    /// Start timing the active story
+    Start,
+    /// Stop the running timer
+    Stop,
+    /// Show the running timer and the active story's total
+    Status,
+    /// Print time spent per story and epic for a week
+    Report {
+        /// Any date within the week to report on (YYYY-MM-DD), defaults to this week
+        #[arg(long)]
+        week: Option<chrono::NaiveDate>,
+    },
 }
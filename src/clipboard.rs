@@ -0,0 +1,23 @@
+use std::sync::Mutex;
+
+use anyhow::Context;
+use arboard::Clipboard;
+
+/// On X11 and Wayland the copied text is served by the process that owns the clipboard,
+/// so it is kept open for as long as we run.
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+pub fn copy(text: &str) -> anyhow::Result<()> {
+    let mut clipboard = CLIPBOARD
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Clipboard lock poisoned"))?;
+
+    let clipboard = match clipboard.as_mut() {
+        Some(clipboard) => clipboard,
+        None => clipboard.insert(Clipboard::new().context("Failed to open the clipboard")?),
+    };
+
+    clipboard
+        .set_text(text)
+        .context("Failed to copy to the clipboard")
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     env,
     path::{Path, PathBuf},
 };
@@ -6,6 +7,11 @@ use std::{
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    branch_name::DEFAULT_BRANCH_TEMPLATE,
+    multiplexer::{MultiplexerKind, SessionLayout},
+};
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub notes_dir: PathBuf,
@@ -13,6 +19,20 @@ pub struct Config {
     pub api_token: String,
     pub editor: String,
     pub repositories_directory: PathBuf,
+    /// Add the labels of a story to the tags of its notes
+    pub mirror_story_labels: bool,
+    /// Commit notes after each editor session, pull on startup and push in the background
+    pub git_sync: bool,
+    /// Pick repos for worktrees with an external fzf instead of the built in picker
+    pub use_fzf: bool,
+    /// Template for story branch names, see `branch_name::branch_name`
+    pub branch_template: String,
+    /// Tmux or zellij, detected from the environment when unset
+    pub multiplexer: Option<MultiplexerKind>,
+    /// Windows and panes of new story sessions
+    pub session_layout: SessionLayout,
+    /// Layouts used instead of `session_layout` for stories in these repos
+    pub session_repo_layouts: HashMap<String, SessionLayout>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -25,6 +45,20 @@ struct ConfigFile {
     editor: String,
     #[serde(default = "default_repositories_directory")]
     repositories_directory: String,
+    #[serde(default)]
+    mirror_story_labels: bool,
+    #[serde(default)]
+    git_sync: bool,
+    #[serde(default)]
+    use_fzf: bool,
+    #[serde(default = "default_branch_template")]
+    branch_template: String,
+    #[serde(default)]
+    multiplexer: Option<MultiplexerKind>,
+    #[serde(default)]
+    session_layout: SessionLayout,
+    #[serde(default)]
+    session_repo_layouts: HashMap<String, SessionLayout>,
 }
 
 impl Default for ConfigFile {
@@ -35,6 +69,13 @@ impl Default for ConfigFile {
             api_token: String::new(),
             editor: default_editor(),
             repositories_directory: default_repositories_directory(),
+            mirror_story_labels: false,
+            git_sync: false,
+            use_fzf: false,
+            branch_template: default_branch_template(),
+            multiplexer: None,
+            session_layout: SessionLayout::default(),
+            session_repo_layouts: HashMap::new(),
         }
     }
 }
@@ -47,6 +88,10 @@ fn default_editor() -> String {
     env::var("EDITOR").unwrap_or_default()
 }
 
+fn default_branch_template() -> String {
+    DEFAULT_BRANCH_TEMPLATE.to_string()
+}
+
 fn default_repositories_directory() -> String {
     "~/Repositories".to_string()
 }
@@ -75,9 +120,22 @@ impl Config {
             api_token: config.api_token,
             editor: config.editor,
             repositories_directory,
+            mirror_story_labels: config.mirror_story_labels,
+            git_sync: config.git_sync,
+            use_fzf: config.use_fzf,
+            branch_template: config.branch_template,
+            multiplexer: config.multiplexer,
+            session_layout: config.session_layout,
+            session_repo_layouts: config.session_repo_layouts,
         })
     }
 
+    /// The session layout for a story in `repo`, falling back to `session_layout`.
+    pub fn session_layout_for(&self, repo: Option<&str>) -> &SessionLayout {
+        repo.and_then(|repo| self.session_repo_layouts.get(repo))
+            .unwrap_or(&self.session_layout)
+    }
+
     pub fn write(&self) -> anyhow::Result<()> {
         let config = ConfigFile {
             notes_dir: self.notes_dir.to_str().unwrap().to_string(),
@@ -85,6 +143,13 @@ impl Config {
             api_token: self.api_token.clone(),
             editor: self.editor.clone(),
             repositories_directory: self.repositories_directory.to_str().unwrap().to_string(),
+            mirror_story_labels: self.mirror_story_labels,
+            git_sync: self.git_sync,
+            use_fzf: self.use_fzf,
+            branch_template: self.branch_template.clone(),
+            multiplexer: self.multiplexer,
+            session_layout: self.session_layout.clone(),
+            session_repo_layouts: self.session_repo_layouts.clone(),
         };
 
         confy::store("shortcut-notes", Some("config"), config).context("Failed to write config")
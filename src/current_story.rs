@@ -0,0 +1,78 @@
+use std::{env, path::Path, process::Command};
+
+use crate::{api::story::Story, branch_name::story_id_from_branch, cache::Cache};
+
+/// Set in story sessions, see `multiplexer::open_story_session`.
+pub const STORY_ID_ENV: &str = "SC_STORY_ID";
+
+/// A story worktree's directory is named after the slugified branch, which keeps its
+/// `sc-1234` part.
+fn story_id_from_worktree_path(cwd: &Path) -> Option<i32> {
+    cwd.ancestors().find_map(|dir| {
+        let parent = dir.parent()?;
+        if parent.file_name()? != ".worktrees" {
+            return None;
+        }
+        story_id_from_branch(dir.file_name()?.to_str()?)
+    })
+}
+
+/// The story we're working on, from `SC_STORY_ID`, then the branch, then the worktree
+/// path of `cwd`.
+pub fn infer_story_id(env_id: Option<&str>, branch: Option<&str>, cwd: &Path) -> Option<i32> {
+    env_id
+        .and_then(|id| id.trim().parse().ok())
+        .or_else(|| branch.and_then(story_id_from_branch))
+        .or_else(|| story_id_from_worktree_path(cwd))
+}
+
+/// The branch checked out in `cwd`, if it is in a git repo and not detached.
+fn current_branch(cwd: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && branch != "HEAD").then_some(branch)
+}
+
+/// The story inferred from the environment and working directory of this process.
+pub fn detect_story_id() -> Option<i32> {
+    let cwd = env::current_dir().ok()?;
+    let env_id = env::var(STORY_ID_ENV).ok();
+    infer_story_id(env_id.as_deref(), current_branch(&cwd).as_deref(), &cwd)
+}
+
+/// The story with `id` among the cached ones.
+pub fn cached_story(cache: &Cache, id: i32) -> Option<&Story> {
+    cache
+        .active_story
+        .iter()
+        .chain(cache.iteration_stories.iter().flatten())
+        .find(|story| story.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_env_then_branch_then_worktree_path() {
+        let worktree = Path::new("/r/api/.worktrees/jane-sc-77-fix-login/src");
+
+        assert_eq!(
+            infer_story_id(Some("12"), Some("jane/sc-34/x"), worktree),
+            Some(12)
+        );
+        assert_eq!(
+            infer_story_id(None, Some("jane/sc-34/x"), worktree),
+            Some(34)
+        );
+        assert_eq!(infer_story_id(Some(""), Some("main"), worktree), Some(77));
+        assert_eq!(
+            infer_story_id(None, Some("main"), Path::new("/r/api/src")),
+            None
+        );
+    }
+}
@@ -1,13 +1,37 @@
 //! Dummy data for development/testing. Enable with DUMMY_DATA=1 env var.
 
-use crate::time;
+use std::{collections::HashMap, path::PathBuf};
 
-use crate::api::{iteration::Iteration, story::Story};
+use crate::{branch_name::DEFAULT_BRANCH_TEMPLATE, config::Config, time};
+
+use crate::api::{
+    branch::{Branch, PullRequest},
+    iteration::Iteration,
+    story::{Story, StoryLabel},
+};
 
 pub fn is_enabled() -> bool {
     std::env::var("DUMMY_DATA").is_ok_and(|val| val == "1")
 }
 
+/// Config with the optional features turned off, for tests that never touch its paths.
+pub fn config() -> Config {
+    Config {
+        notes_dir: PathBuf::from("/tmp/notes"),
+        cache_dir: PathBuf::from("/tmp/cache"),
+        api_token: String::new(),
+        editor: "vi".to_string(),
+        repositories_directory: PathBuf::from("/tmp/repos"),
+        mirror_story_labels: false,
+        git_sync: false,
+        use_fzf: false,
+        branch_template: DEFAULT_BRANCH_TEMPLATE.to_string(),
+        multiplexer: None,
+        session_layout: Default::default(),
+        session_repo_layouts: HashMap::new(),
+    }
+}
+
 pub fn iteration() -> Iteration {
     let today = time::today();
     Iteration {
@@ -27,7 +51,36 @@ pub fn stories() -> Vec<Story> {
             name: "Implement user authentication".to_string(),
             description: "Add login/logout functionality with OAuth2.\n\nAcceptance criteria:\n- Users can log in with Google\n- Session persists across browser refresh\n- Logout clears all tokens".to_string(),
             completed: false,
-            branches: vec![],
+            started: true,
+            labels: vec![
+                StoryLabel {
+                    name: "auth".to_string(),
+                },
+                StoryLabel {
+                    name: "backend".to_string(),
+                },
+            ],
+            estimate: Some(3),
+            blocked: false,
+            moved_at: Some(chrono::Utc::now() - chrono::Duration::hours(20)),
+            story_type: "feature".to_string(),
+            pull_requests: vec![],
+            branches: vec![Branch {
+                id: 1,
+                name: "jane/sc-101/implement-user-authentication".to_string(),
+                deleted: false,
+                pull_requests: vec![PullRequest {
+                    id: 1,
+                    number: 42,
+                    title: "Add OAuth2 login".to_string(),
+                    url: "https://github.com/example/api/pull/42".to_string(),
+                    branch_name: Some("jane/sc-101/implement-user-authentication".to_string()),
+                    closed: false,
+                    merged: false,
+                    draft: false,
+                    review_status: Some("changes_requested".to_string()),
+                }],
+            }],
             comments: vec![],
             epic_id: Some(10),
             iteration_id: Some(1),
@@ -38,6 +91,17 @@ pub fn stories() -> Vec<Story> {
             name: "Fix pagination bug on search results".to_string(),
             description: "When there are more than 100 results, the pagination breaks and shows duplicate items on page 2.".to_string(),
             completed: false,
+            started: false,
+            labels: vec![
+                StoryLabel {
+                    name: "bug".to_string(),
+                },
+            ],
+            estimate: Some(1),
+            blocked: false,
+            moved_at: None,
+            story_type: "bug".to_string(),
+            pull_requests: vec![],
             branches: vec![],
             comments: vec![],
             epic_id: None,
@@ -49,6 +113,17 @@ pub fn stories() -> Vec<Story> {
             name: "Add dark mode support".to_string(),
             description: "Implement system-aware dark mode with manual toggle.\n\nDesign specs in Figma.".to_string(),
             completed: false,
+            started: true,
+            labels: vec![
+                StoryLabel {
+                    name: "frontend".to_string(),
+                },
+            ],
+            estimate: Some(2),
+            blocked: false,
+            moved_at: Some(chrono::Utc::now() - chrono::Duration::hours(2)),
+            story_type: "feature".to_string(),
+            pull_requests: vec![],
             branches: vec![],
             comments: vec![],
             epic_id: Some(10),
@@ -60,6 +135,17 @@ pub fn stories() -> Vec<Story> {
             name: "Refactor database connection pooling".to_string(),
             description: "Current implementation creates new connections for each request. Switch to connection pooling with configurable limits.\n\nBenchmark before/after.".to_string(),
             completed: false,
+            started: false,
+            labels: vec![
+                StoryLabel {
+                    name: "backend".to_string(),
+                },
+            ],
+            estimate: Some(5),
+            blocked: true,
+            moved_at: None,
+            story_type: "chore".to_string(),
+            pull_requests: vec![],
             branches: vec![],
             comments: vec![],
             epic_id: Some(20),
@@ -71,6 +157,13 @@ pub fn stories() -> Vec<Story> {
             name: "Write API documentation".to_string(),
             description: "Document all public endpoints with examples.".to_string(),
             completed: false,
+            started: false,
+            labels: vec![],
+            estimate: None,
+            blocked: false,
+            moved_at: None,
+            story_type: "feature".to_string(),
+            pull_requests: vec![],
             branches: vec![],
             comments: vec![],
             epic_id: None,
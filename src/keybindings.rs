@@ -13,13 +13,16 @@ pub enum Key {
     ViewNext,
     ViewPrev,
     DailyNote,
+    Capture,
+    Standup,
+    Timer,
     // Story list
     Description,     // Space
     IterationNote,   // i
     OpenNote,        // n
     OpenBrowser,     // o
     EditDescription, // e
-    Tmux,            // t
+    Session,         // t
     SelectStory,     // a
     ToggleFinished,  // f
 }
@@ -34,13 +37,19 @@ impl Key {
             KeyCode::Char('q') => Some(Key::Quit),
             KeyCode::Char('?') => Some(Key::HelpPanel),
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Key::Quit),
-            KeyCode::Char('d') => Some(Key::DailyNote),
+            // ctrl+d is left free for scrolling
+            KeyCode::Char('d') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Key::DailyNote)
+            }
+            KeyCode::Char('c') => Some(Key::Capture),
+            KeyCode::Char('S') => Some(Key::Standup),
+            KeyCode::Char('T') => Some(Key::Timer),
             KeyCode::Char(' ') => Some(Key::Description),
             KeyCode::Char('i') => Some(Key::IterationNote),
             KeyCode::Char('n') => Some(Key::OpenNote),
             KeyCode::Char('o') => Some(Key::OpenBrowser),
             KeyCode::Char('e') => Some(Key::EditDescription),
-            KeyCode::Char('t') => Some(Key::Tmux),
+            KeyCode::Char('t') => Some(Key::Session),
             KeyCode::Char('a') => Some(Key::SelectStory),
             KeyCode::Char('f') => Some(Key::ToggleFinished),
             _ => None,
@@ -56,12 +65,15 @@ impl Key {
             Key::ViewNext => "Next view",
             Key::ViewPrev => "Previous view",
             Key::DailyNote => "Open daily note",
+            Key::Capture => "Quick capture",
+            Key::Standup => "Standup summary",
+            Key::Timer => "Start/stop timer on active story",
             Key::Description => "Show story description",
             Key::IterationNote => "Open iteration note",
             Key::OpenNote => "Open story note",
             Key::OpenBrowser => "Open in browser",
             Key::EditDescription => "Edit story description",
-            Key::Tmux => "Open tmux session",
+            Key::Session => "Open session",
             Key::SelectStory => "Select as active story",
             Key::ToggleFinished => "Toggle show finished",
         }
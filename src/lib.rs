@@ -1,4 +1,4 @@
-use std::{fs, process::Command};
+use std::fs;
 
 use anyhow::Context;
 use ratatui::DefaultTerminal;
@@ -6,33 +6,49 @@ use uuid::Uuid;
 
 use crate::{
     api::{
+        ApiClient,
+        iteration::Iteration,
         story::{Story, get_story_associated_iteration},
-        user::get_user_id_from_api,
-    },
-    app::{
-        App,
-        cmd::{open_note_in_editor, open_tmux_session},
+        user::{get_member_from_api, get_user_id_from_api},
     },
+    app::{App, cmd::open_note_in_editor},
+    branch_name::branch_name,
     cache::Cache,
-    cli::Commands,
+    cli::{Commands, NotesCommands, ReportCommands, TimerCommands},
     config::Config,
-    note::Note,
+    current_story::{cached_story, detect_story_id},
+    multiplexer::{from_config, open_story_session},
+    note::{
+        Note,
+        capture::{CaptureTarget, capture},
+        frontmatter::SCHEMA_VERSION,
+        manage::{archive_note, finished_notes},
+        migrate::plan_migrations,
+        report::{build_iteration_report, write_iteration_report},
+        standup::build_standup,
+        sync::{SyncStatus, commit_and_push, commit_message},
+    },
+    timer::{TimeLog, format_duration},
 };
 
 pub mod api;
 pub mod app;
+pub mod branch_name;
 pub mod cache;
 pub mod cli;
+pub mod clipboard;
 pub mod config;
+pub mod current_story;
 pub mod custom_list;
 pub mod dummy;
 pub mod error;
 pub mod keybindings;
 pub mod macros;
+pub mod multiplexer;
 pub mod note;
 pub mod text_utils;
 pub mod time;
-pub mod tmux;
+pub mod timer;
 pub mod view;
 pub mod worktree;
 #[macro_use]
@@ -40,7 +56,10 @@ pub mod keys;
 
 macro_rules! no_active_story {
     () => {
-        anyhow::bail!("You do not have a currently active story");
+        anyhow::bail!(
+            "No story here: not on a story branch or in a story worktree, SC_STORY_ID isn't set \
+             and you do not have a currently active story"
+        );
     };
 }
 
@@ -54,6 +73,107 @@ pub async fn get_user_id(saved_user_id: Option<Uuid>, api_token: &str) -> anyhow
     Ok(id)
 }
 
+/// The user's Shortcut mention name, fetched and cached when it isn't cached yet. The fetch
+/// caches the user ID too, so a following `get_user_id` doesn't need the API.
+pub async fn get_mention_name(cache: &mut Cache, api_token: &str) -> anyhow::Result<String> {
+    if let Some(name) = &cache.mention_name {
+        return Ok(name.clone());
+    }
+
+    let member = get_member_from_api(api_token).await?;
+    cache.user_id = Some(member.id);
+    cache.mention_name = Some(member.mention_name.clone());
+    Ok(member.mention_name)
+}
+
+/// The story inferred from `SC_STORY_ID`, the branch or the worktree we're in, falling back
+/// to the active story. Inferred stories that aren't cached are fetched.
+async fn current_story(cache: &Cache, config: &Config) -> anyhow::Result<Option<Story>> {
+    let Some(id) = detect_story_id() else {
+        return Ok(cache.active_story.clone());
+    };
+    if let Some(story) = cached_story(cache, id) {
+        return Ok(Some(story.clone()));
+    }
+
+    let user_id = get_user_id(cache.user_id, &config.api_token).await?;
+    let api_client = ApiClient::new(config.api_token.clone(), user_id);
+    Ok(Some(api_client.get_story(id).await?))
+}
+
+/// The user's stories in the current iterations, fetched and cached the way the TUI loads
+/// them, so commands and the TUI work from the same stories. Falls back to the cached
+/// stories when the API can't be reached.
+async fn iteration_stories(cache: &mut Cache, config: &Config) -> Vec<Story> {
+    let fetched = async {
+        let user_id = get_user_id(cache.user_id, &config.api_token).await?;
+        let api_client = ApiClient::new(config.api_token.clone(), user_id);
+        let iterations = match &cache.current_iterations {
+            Some(iterations) => iterations.clone(),
+            None => api_client.get_current_iterations().await?,
+        };
+        let ids = iterations.iter().map(|it| it.id).collect();
+        api_client.get_owned_iteration_stories(ids).await
+    };
+
+    match fetched.await {
+        Ok(stories) => {
+            cache.iteration_stories = Some(stories.clone());
+            if let Err(e) = cache.write().await {
+                eprintln!("Failed to cache stories: {:#}", e);
+            }
+            stories
+        }
+        Err(e) => {
+            eprintln!("Using cached stories, failed to fetch them: {:#}", e);
+            cache.iteration_stories.clone().unwrap_or_default()
+        }
+    }
+}
+
+/// Finds an iteration by id or name, or the one running today for `current`. Cached
+/// iterations are tried before asking the API.
+async fn resolve_iteration(
+    query: &str,
+    cache: &Cache,
+    api_client: &ApiClient,
+) -> anyhow::Result<Iteration> {
+    if query == "current" {
+        let current = match &cache.current_iterations {
+            Some(iterations) if !iterations.is_empty() => iterations.clone(),
+            _ => api_client.get_current_iterations().await?,
+        };
+
+        return match current.as_slice() {
+            [] => anyhow::bail!("No iteration is running today"),
+            [iteration] => Ok(iteration.clone()),
+            several => {
+                let names: Vec<_> = several.iter().map(|it| it.name.as_str()).collect();
+                anyhow::bail!(
+                    "Several iterations are running: {}. Pass a name instead",
+                    names.join(", ")
+                )
+            }
+        };
+    }
+
+    let matches = |it: &&Iteration| {
+        query.parse::<i32>().is_ok_and(|id| id == it.id) || it.name.eq_ignore_ascii_case(query)
+    };
+
+    if let Some(iteration) = cache.iterations.iter().find(matches) {
+        return Ok(iteration.clone());
+    }
+
+    api_client
+        .get_all_iterations()
+        .await?
+        .iter()
+        .find(matches)
+        .cloned()
+        .with_context(|| format!("No iteration named {}", query))
+}
+
 pub async fn run(terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
     let mut app = App::init().await?;
     app.main_loop(terminal).await?;
@@ -64,12 +184,12 @@ pub async fn run(terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
 
 pub async fn handle_command(
     command: Commands,
-    cache: Cache,
+    mut cache: Cache,
     config: &Config,
 ) -> anyhow::Result<()> {
     match command {
         Commands::Note => {
-            if let Some(story) = &cache.active_story {
+            if let Some(story) = &current_story(&cache, config).await? {
                 let iteration_app_url = cache
                     .current_iterations_ref()
                     .and_then(|iterations| {
@@ -85,6 +205,18 @@ pub async fn handle_command(
                     config,
                 )?;
 
+                if config.git_sync {
+                    let message = commit_message(Some(story.id), &story.name);
+                    if let SyncStatus::Conflicts(files) =
+                        commit_and_push(&config.notes_dir, &message).await?
+                    {
+                        eprintln!(
+                            "Notes committed but not pushed, conflicts in: {}",
+                            files.join(", ")
+                        );
+                    }
+                }
+
                 Ok(())
             } else {
                 no_active_story!();
@@ -92,9 +224,9 @@ pub async fn handle_command(
         }
 
         Commands::Tmux => {
-            if let Some(story) = &cache.active_story {
-                let session_name = Story::tmux_session_name(&story.name);
-                open_tmux_session(&session_name).await?;
+            if let Some(story) = &current_story(&cache, config).await? {
+                let mux = from_config(config);
+                open_story_session(&*mux, config, &cache, story)?;
                 Ok(())
             } else {
                 no_active_story!();
@@ -108,7 +240,7 @@ pub async fn handle_command(
         }
 
         Commands::Open => {
-            if let Some(story) = &cache.active_story {
+            if let Some(story) = &current_story(&cache, config).await? {
                 open::that(story.app_url.clone())
                     .with_context(|| format!("Failed to open {}", story.app_url))
             } else {
@@ -117,7 +249,7 @@ pub async fn handle_command(
         }
 
         Commands::Cat => {
-            if let Some(story) = &cache.active_story {
+            if let Some(story) = &current_story(&cache, config).await? {
                 let iteration_app_url = cache
                     .current_iterations_ref()
                     .and_then(|iterations| {
@@ -133,16 +265,198 @@ pub async fn handle_command(
                     iteration_app_url,
                 );
 
-                let status = Command::new("cat").arg(note.path).status()?;
-
-                if !status.success() {
-                    anyhow::bail!("Failed to cat not for story {}", &story.name);
-                }
+                let contents = fs::read_to_string(&note.path).with_context(|| {
+                    format!("Failed to read note for story {}", &story.name)
+                })?;
+                print!("{}", contents);
 
                 Ok(())
             } else {
                 no_active_story!();
             }
         }
+
+        Commands::Capture { text } => {
+            let stories = cache.iteration_stories.clone().unwrap_or_default();
+            let story = current_story(&cache, config).await?;
+            let target = CaptureTarget::resolve(
+                story.as_ref(),
+                &stories,
+                cache.current_iterations.as_deref().unwrap_or(&[]),
+            );
+
+            let path = capture(
+                &config.notes_dir,
+                target,
+                &text.join(" "),
+                crate::time::now_naive(),
+            )?;
+            println!("Captured to {}", path.display());
+
+            Ok(())
+        }
+
+        Commands::Branch { copy } => {
+            let Some(story) = current_story(&cache, config).await? else {
+                no_active_story!();
+            };
+
+            let mention_name = get_mention_name(&mut cache, &config.api_token).await?;
+            let name = branch_name(&config.branch_template, &story, Some(&mention_name));
+            println!("{}", name);
+
+            if copy {
+                crate::clipboard::copy(&name)?;
+            }
+            cache.write().await?;
+
+            Ok(())
+        }
+
+        Commands::Timer { command } => {
+            let mut time_log = TimeLog::load(&config.cache_dir)?;
+            let now = chrono::Utc::now();
+
+            match command {
+                TimerCommands::Start => {
+                    let Some(story) = &current_story(&cache, config).await? else {
+                        no_active_story!();
+                    };
+                    time_log.start(story.into(), now);
+                    time_log.save(&config.cache_dir)?;
+                    println!("Started timer for sc-{} {}", story.id, story.name);
+                }
+                TimerCommands::Stop => match time_log.stop(now) {
+                    Some(session) => {
+                        time_log.save(&config.cache_dir)?;
+                        println!(
+                            "Stopped timer for sc-{} after {}",
+                            session.story.id,
+                            format_duration(session.end - session.start)
+                        );
+                    }
+                    None => println!("No timer running"),
+                },
+                TimerCommands::Status => {
+                    match &time_log.running {
+                        Some(running) => println!(
+                            "Running: sc-{} {} ({})",
+                            running.story.id,
+                            running.story.name,
+                            format_duration(now - running.start)
+                        ),
+                        None => println!("No timer running"),
+                    }
+                    if let Some(story) = &current_story(&cache, config).await? {
+                        println!(
+                            "Total for sc-{}: {}",
+                            story.id,
+                            format_duration(time_log.story_total(story.id, now))
+                        );
+                    }
+                }
+                TimerCommands::Report { week } => {
+                    let date = week.unwrap_or_else(crate::time::today);
+                    print!("{}", time_log.weekly_report(date, &cache.epics, now));
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Standup => {
+            let stories = iteration_stories(&mut cache, config).await;
+            let standup = build_standup(&stories, &config.notes_dir, crate::time::today());
+            print!("{}", standup.to_text());
+            Ok(())
+        }
+
+        Commands::Report {
+            command: ReportCommands::Iteration { iteration, stdout },
+        } => {
+            let user_id = get_user_id(cache.user_id, &config.api_token).await?;
+            let api_client = ApiClient::new(config.api_token.clone(), user_id);
+
+            let iteration = resolve_iteration(&iteration, &cache, &api_client).await?;
+            let stories = api_client.get_iteration_stories(iteration.id).await?;
+
+            if stdout {
+                let today = crate::time::today();
+                print!(
+                    "{}",
+                    build_iteration_report(&config.notes_dir, &iteration, &stories, today)
+                );
+            } else {
+                let path = write_iteration_report(&config.notes_dir, &iteration, &stories)?;
+                println!("Wrote {}", path.display());
+            }
+
+            Ok(())
+        }
+
+        Commands::Notes {
+            command: NotesCommands::Archive { dry_run },
+        } => {
+            let stories = cache.iteration_stories.clone().unwrap_or_default();
+            let notes = finished_notes(
+                &config.notes_dir,
+                &stories,
+                &cache.iterations,
+                crate::time::today(),
+            );
+
+            if notes.is_empty() {
+                println!("No notes to archive");
+                return Ok(());
+            }
+
+            for path in notes {
+                if dry_run {
+                    println!("would archive {}", path.display());
+                    continue;
+                }
+
+                let archived = archive_note(&config.notes_dir, &path)?;
+                println!("{} -> {}", path.display(), archived.display());
+            }
+
+            Ok(())
+        }
+
+        Commands::Notes {
+            command: NotesCommands::Migrate { dry_run },
+        } => {
+            let migrations = plan_migrations(&config.notes_dir);
+
+            if migrations.is_empty() {
+                println!("All notes are at schema version {}", SCHEMA_VERSION);
+                return Ok(());
+            }
+
+            for migration in &migrations {
+                let path = migration
+                    .path
+                    .strip_prefix(&config.notes_dir)
+                    .unwrap_or(&migration.path);
+                println!(
+                    "{} (v{} -> v{})",
+                    path.display(),
+                    migration.from_version,
+                    SCHEMA_VERSION
+                );
+
+                if dry_run {
+                    print!("{}", migration.diff(&path.display().to_string()));
+                } else {
+                    migration.apply()?;
+                }
+            }
+
+            if !dry_run {
+                println!("Migrated {} notes", migrations.len());
+            }
+
+            Ok(())
+        }
     }
 }
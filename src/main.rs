@@ -1,12 +1,8 @@
 use clap::Parser;
-use shortcut_notes::{
-    cache::Cache, cli::Cli, config::Config, worktree::check_worktree_dependencies,
-};
+use shortcut_notes::{cache::Cache, cli::Cli, config::Config};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    check_worktree_dependencies()?;
-
     let args = Cli::parse();
     if let Some(cmd) = args.command {
         let config = Config::read()?;
@@ -0,0 +1,253 @@
+use std::{env, path::Path, process::Output};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::story::Story, cache::Cache, config::Config, current_story::STORY_ID_ENV, note::Note,
+    worktree::story_repo,
+};
+
+pub mod tmux;
+pub mod zellij;
+
+/// Prefix of the sessions created for stories, see `Story::tmux_session_name`.
+pub const SESSION_PREFIX: &str = "scn--";
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiplexerKind {
+    Tmux,
+    Zellij,
+}
+
+impl MultiplexerKind {
+    /// The multiplexer we are running inside of, tmux otherwise.
+    pub fn detect() -> Self {
+        if env::var("ZELLIJ").is_ok() {
+            MultiplexerKind::Zellij
+        } else {
+            MultiplexerKind::Tmux
+        }
+    }
+}
+
+/// Windows, called tabs in zellij, created in a new story session. Without any, the
+/// session has the multiplexer's single default window.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionLayout {
+    #[serde(default)]
+    pub windows: Vec<SessionWindow>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SessionWindow {
+    pub name: String,
+    /// Command run in each pane, an empty one leaves a plain shell
+    #[serde(default)]
+    pub panes: Vec<String>,
+    /// A tmux layout such as `main-vertical` or `tiled`, applied once the panes exist.
+    /// Zellij stacks the panes instead.
+    #[serde(default)]
+    pub layout: Option<String>,
+}
+
+/// Everything needed to create a story session.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionSpec<'a> {
+    pub name: &'a str,
+    pub story_id: i32,
+    pub dir: Option<&'a Path>,
+    pub layout: &'a SessionLayout,
+    /// Set for every shell in the session
+    pub env: &'a [(&'a str, String)],
+}
+
+/// A story session as listed by the multiplexer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub name: String,
+    pub attached: bool,
+    /// Unknown for zellij sessions
+    pub windows: Option<usize>,
+    /// Missing for sessions created before it was stored
+    pub story_id: Option<i32>,
+}
+
+/// The session operations story features need, so they work the same with tmux and
+/// zellij. Calls block on the multiplexer's CLI.
+pub trait Multiplexer: Send + Sync {
+    /// Whether we are running inside one of its sessions, so attaching switches the
+    /// client instead of taking over the terminal.
+    fn inside_session(&self) -> bool;
+
+    /// Name of a new session for the story.
+    fn session_name(&self, story: &Story) -> String {
+        Story::tmux_session_name(&story.name)
+    }
+
+    fn session_exists(&self, name: &str) -> anyhow::Result<bool> {
+        Ok(self.list_sessions()?.iter().any(|s| s.name == name))
+    }
+
+    /// Creates a detached session.
+    fn create_session(&self, spec: &SessionSpec) -> anyhow::Result<()>;
+
+    /// Attaches to the session, or switches to it from inside another one.
+    fn attach_session(&self, name: &str) -> anyhow::Result<()>;
+
+    /// Every story session, none when the multiplexer isn't running.
+    fn list_sessions(&self) -> anyhow::Result<Vec<SessionInfo>>;
+
+    fn kill_session(&self, name: &str) -> anyhow::Result<()>;
+}
+
+/// Turns a failed multiplexer command into an error with its stderr, `backend` names the
+/// multiplexer in the message.
+fn error_on_command_fail(output: &Output, backend: &str) -> anyhow::Result<()> {
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "{} command failed with error: {:?}",
+            backend,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The multiplexer set in the config, or the one we are running inside of.
+pub fn from_config(config: &Config) -> Box<dyn Multiplexer> {
+    match config.multiplexer.unwrap_or_else(MultiplexerKind::detect) {
+        MultiplexerKind::Tmux => Box::new(tmux::Tmux),
+        MultiplexerKind::Zellij => Box::new(zellij::Zellij::new(&config.cache_dir)),
+    }
+}
+
+/// The story's existing session, found by story ID so a rename doesn't lose it, or by name.
+pub fn find_story_session(
+    mux: &dyn Multiplexer,
+    story: &Story,
+) -> anyhow::Result<Option<SessionInfo>> {
+    let name = Story::tmux_session_name(&story.name);
+    Ok(mux
+        .list_sessions()?
+        .into_iter()
+        .find(|s| s.story_id == Some(story.id) || s.name == name))
+}
+
+/// Attaches to the story's session, creating it in the story's worktree or repo with the
+/// configured layout. `SC_STORY_ID` and `SC_NOTE_PATH` are set for the shells in it.
+pub fn open_story_session(
+    mux: &dyn Multiplexer,
+    config: &Config,
+    cache: &Cache,
+    story: &Story,
+) -> anyhow::Result<()> {
+    let name = match find_story_session(mux, story)? {
+        Some(session) => session.name,
+        None => {
+            let name = mux.session_name(story);
+            let repo = story_repo(config, cache, story);
+            let note = Note::new(
+                &config.notes_dir,
+                story.id,
+                story.name.clone(),
+                story.app_url.clone(),
+                None,
+            );
+            let env = [
+                (STORY_ID_ENV, story.id.to_string()),
+                ("SC_NOTE_PATH", note.path.display().to_string()),
+            ];
+            mux.create_session(&SessionSpec {
+                name: &name,
+                story_id: story.id,
+                dir: repo.as_ref().map(|r| r.work_dir()),
+                layout: config.session_layout_for(repo.as_ref().map(|r| r.repo.as_str())),
+                env: &env,
+            })?;
+            name
+        }
+    };
+
+    mux.attach_session(&name)
+}
+
+#[cfg(test)]
+pub mod fake {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Keeps sessions in memory and records what was attached.
+    #[derive(Default)]
+    pub struct FakeMultiplexer {
+        pub sessions: Mutex<Vec<SessionInfo>>,
+        pub attached: Mutex<Vec<String>>,
+    }
+
+    impl Multiplexer for FakeMultiplexer {
+        fn inside_session(&self) -> bool {
+            true
+        }
+
+        fn create_session(&self, spec: &SessionSpec) -> anyhow::Result<()> {
+            if self.session_exists(spec.name)? {
+                anyhow::bail!("duplicate session: {}", spec.name);
+            }
+            self.sessions.lock().unwrap().push(SessionInfo {
+                name: spec.name.to_string(),
+                attached: false,
+                windows: Some(spec.layout.windows.len().max(1)),
+                story_id: Some(spec.story_id),
+            });
+            Ok(())
+        }
+
+        fn attach_session(&self, name: &str) -> anyhow::Result<()> {
+            let mut sessions = self.sessions.lock().unwrap();
+            let session = sessions
+                .iter_mut()
+                .find(|s| s.name == name)
+                .ok_or_else(|| anyhow::anyhow!("no session: {}", name))?;
+            session.attached = true;
+            self.attached.lock().unwrap().push(name.to_string());
+            Ok(())
+        }
+
+        fn list_sessions(&self) -> anyhow::Result<Vec<SessionInfo>> {
+            Ok(self.sessions.lock().unwrap().clone())
+        }
+
+        fn kill_session(&self, name: &str) -> anyhow::Result<()> {
+            self.sessions.lock().unwrap().retain(|s| s.name != name);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fake::FakeMultiplexer, *};
+    use crate::dummy;
+
+    #[test]
+    fn reuses_the_session_of_a_renamed_story() {
+        let config = dummy::config();
+        let cache = Cache::default();
+        let mux = FakeMultiplexer::default();
+        let mut story = dummy::stories().remove(0);
+
+        open_story_session(&mux, &config, &cache, &story).unwrap();
+        let created = Story::tmux_session_name(&story.name);
+
+        story.name = "A new name".to_string();
+        open_story_session(&mux, &config, &cache, &story).unwrap();
+
+        assert_eq!(mux.list_sessions().unwrap().len(), 1);
+        assert_eq!(
+            *mux.attached.lock().unwrap(),
+            vec![created.clone(), created]
+        );
+    }
+}
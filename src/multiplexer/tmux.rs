@@ -0,0 +1,254 @@
+use std::{
+    env,
+    process::{Command, Output},
+};
+
+use crate::{
+    dbg_file,
+    multiplexer::{Multiplexer, SESSION_PREFIX, SessionInfo, SessionSpec, error_on_command_fail},
+};
+
+/// Session option holding the story ID, so a session still maps to its story after a
+/// rename changes the slug.
+const STORY_ID_OPTION: &str = "@sc_story_id";
+
+fn tmux(args: &[&str]) -> anyhow::Result<Output> {
+    dbg_file!("Running tmux {:?}", args);
+    Ok(Command::new("tmux").args(args).output()?)
+}
+
+fn parse_sessions(output: &str) -> Vec<SessionInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?;
+            if !name.starts_with(SESSION_PREFIX) {
+                return None;
+            }
+            Some(SessionInfo {
+                name: name.to_string(),
+                attached: fields.next().is_some_and(|n| n != "0"),
+                windows: fields.next().and_then(|n| n.parse().ok()),
+                story_id: fields.next().and_then(|id| id.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// Tmux invocations that create the session, build the windows and panes of its layout and
+/// store the story ID.
+fn create_commands(spec: &SessionSpec) -> Vec<Vec<String>> {
+    let SessionSpec {
+        name,
+        story_id,
+        dir,
+        layout,
+        env,
+    } = *spec;
+    let dir_args = |args: &mut Vec<String>| {
+        if let Some(dir) = dir {
+            args.extend(["-c".to_string(), dir.display().to_string()]);
+        }
+    };
+
+    let mut new_session = vec!["new-session", "-d", "-s", name]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    dir_args(&mut new_session);
+    for (key, value) in env {
+        new_session.extend(["-e".to_string(), format!("{}={}", key, value)]);
+    }
+    if let Some(first) = layout.windows.first() {
+        new_session.extend(["-n".to_string(), first.name.clone()]);
+    }
+
+    let set_story_id = vec![
+        "set-option".to_string(),
+        "-t".to_string(),
+        name.to_string(),
+        STORY_ID_OPTION.to_string(),
+        story_id.to_string(),
+    ];
+
+    let mut commands = vec![new_session, set_story_id];
+    for (idx, window) in layout.windows.iter().enumerate() {
+        let target = format!("{}:{}", name, window.name);
+        if idx > 0 {
+            let mut new_window = vec![
+                "new-window".to_string(),
+                "-t".to_string(),
+                format!("{}:", name),
+                "-n".to_string(),
+                window.name.clone(),
+            ];
+            dir_args(&mut new_window);
+            commands.push(new_window);
+        }
+
+        for (pane_idx, pane) in window.panes.iter().enumerate() {
+            if pane_idx > 0 {
+                // the new pane becomes the active one, which send-keys targets
+                let mut split = vec!["split-window".to_string(), "-t".to_string(), target.clone()];
+                dir_args(&mut split);
+                commands.push(split);
+            }
+            if !pane.is_empty() {
+                commands.push(vec![
+                    "send-keys".to_string(),
+                    "-t".to_string(),
+                    target.clone(),
+                    pane.clone(),
+                    "Enter".to_string(),
+                ]);
+            }
+        }
+
+        if let Some(tmux_layout) = &window.layout {
+            commands.push(vec![
+                "select-layout".to_string(),
+                "-t".to_string(),
+                target.clone(),
+                tmux_layout.clone(),
+            ]);
+        }
+    }
+
+    if let Some(first) = layout.windows.first() {
+        commands.push(vec![
+            "select-window".to_string(),
+            "-t".to_string(),
+            format!("{}:{}", name, first.name),
+        ]);
+    }
+
+    commands
+}
+
+pub struct Tmux;
+
+impl Multiplexer for Tmux {
+    fn inside_session(&self) -> bool {
+        env::var("TMUX").is_ok()
+    }
+
+    fn create_session(&self, spec: &SessionSpec) -> anyhow::Result<()> {
+        for args in create_commands(spec) {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            error_on_command_fail(&tmux(&args)?, "Tmux")?;
+        }
+        Ok(())
+    }
+
+    fn attach_session(&self, name: &str) -> anyhow::Result<()> {
+        if self.inside_session() {
+            error_on_command_fail(&tmux(&["switch-client", "-t", name])?, "Tmux")
+        } else {
+            // takes over the terminal until the user detaches
+            let status = Command::new("tmux")
+                .args(["attach-session", "-t", name])
+                .status()?;
+            if !status.success() {
+                anyhow::bail!("tmux attach-session failed for {}", name);
+            }
+            Ok(())
+        }
+    }
+
+    fn list_sessions(&self) -> anyhow::Result<Vec<SessionInfo>> {
+        let format = format!(
+            "#{{session_name}}\t#{{session_attached}}\t#{{session_windows}}\t#{{{}}}",
+            STORY_ID_OPTION
+        );
+        let output = tmux(&["list-sessions", "-F", &format])?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success()
+            && (stderr.contains("no server running") || stderr.contains("error connecting"))
+        {
+            return Ok(Vec::new());
+        }
+        error_on_command_fail(&output, "Tmux")?;
+
+        Ok(parse_sessions(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn kill_session(&self, name: &str) -> anyhow::Result<()> {
+        error_on_command_fail(&tmux(&["kill-session", "-t", name])?, "Tmux")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::multiplexer::{SessionLayout, SessionWindow};
+
+    #[test]
+    fn builds_windows_and_panes_from_layout() {
+        let layout = SessionLayout {
+            windows: vec![
+                SessionWindow {
+                    name: "editor".to_string(),
+                    panes: vec!["nvim".to_string()],
+                    layout: None,
+                },
+                SessionWindow {
+                    name: "dev".to_string(),
+                    panes: vec![String::new(), "cargo test".to_string()],
+                    layout: Some("even-horizontal".to_string()),
+                },
+            ],
+        };
+        let env = [("SC_STORY_ID", "12".to_string())];
+        let spec = SessionSpec {
+            name: "scn--x",
+            story_id: 12,
+            dir: Some(Path::new("/r/api")),
+            layout: &layout,
+            env: &env,
+        };
+        let commands: Vec<String> = create_commands(&spec)
+            .iter()
+            .map(|args| args.join(" "))
+            .collect();
+
+        assert_eq!(
+            commands,
+            vec![
+                "new-session -d -s scn--x -c /r/api -e SC_STORY_ID=12 -n editor",
+                "set-option -t scn--x @sc_story_id 12",
+                "send-keys -t scn--x:editor nvim Enter",
+                "new-window -t scn--x: -n dev -c /r/api",
+                "split-window -t scn--x:dev -c /r/api",
+                "send-keys -t scn--x:dev cargo test Enter",
+                "select-layout -t scn--x:dev even-horizontal",
+                "select-window -t scn--x:editor",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_only_story_sessions() {
+        let output = "scn--fix-login\t1\t3\t42\nmisc\t0\t1\t\nscn--old\t0\t2\t\n";
+        assert_eq!(
+            parse_sessions(output),
+            vec![
+                SessionInfo {
+                    name: "scn--fix-login".to_string(),
+                    attached: true,
+                    windows: Some(3),
+                    story_id: Some(42),
+                },
+                SessionInfo {
+                    name: "scn--old".to_string(),
+                    attached: false,
+                    windows: Some(2),
+                    story_id: None,
+                },
+            ]
+        );
+    }
+}
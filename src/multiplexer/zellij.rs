@@ -0,0 +1,262 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    api::story::Story,
+    dbg_file,
+    multiplexer::{Multiplexer, SESSION_PREFIX, SessionInfo, SessionSpec, error_on_command_fail},
+};
+
+/// Zellij can't store the story ID with a session, so it goes in the session name instead,
+/// see `story_session_name`.
+pub struct Zellij {
+    /// Where generated layouts are written, zellij reads them when a session starts
+    layout_dir: PathBuf,
+}
+
+impl Zellij {
+    pub fn new(cache_dir: &Path) -> Self {
+        Self {
+            layout_dir: cache_dir.join("zellij"),
+        }
+    }
+}
+
+/// `scn--sc-12--fix-login`, slugs never contain `--` so the ID can be read back.
+fn story_session_name(story: &Story) -> String {
+    let slug = Story::tmux_session_name(&story.name);
+    let slug = slug.strip_prefix(SESSION_PREFIX).unwrap_or(&slug);
+    format!("{}sc-{}--{}", SESSION_PREFIX, story.id, slug)
+}
+
+/// The story ID in a name from `story_session_name`.
+fn session_story_id(name: &str) -> Option<i32> {
+    let (id, _) = name
+        .strip_prefix(SESSION_PREFIX)?
+        .strip_prefix("sc-")?
+        .split_once("--")?;
+    id.parse().ok()
+}
+
+fn kdl_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A KDL layout with a tab per window, keeping zellij's tab and status bars.
+fn layout_kdl(spec: &SessionSpec) -> String {
+    let mut kdl = String::from("layout {\n");
+    if let Some(dir) = spec.dir {
+        kdl.push_str(&format!(
+            "    cwd {}\n",
+            kdl_string(&dir.display().to_string())
+        ));
+    }
+    kdl.push_str(
+        "    default_tab_template {\n\
+         \x20       pane size=1 borderless=true {\n\
+         \x20           plugin location=\"zellij:tab-bar\"\n\
+         \x20       }\n\
+         \x20       children\n\
+         \x20       pane size=2 borderless=true {\n\
+         \x20           plugin location=\"zellij:status-bar\"\n\
+         \x20       }\n\
+         \x20   }\n",
+    );
+
+    for (idx, window) in spec.layout.windows.iter().enumerate() {
+        let focus = if idx == 0 { " focus=true" } else { "" };
+        kdl.push_str(&format!(
+            "    tab name={}{} {{\n",
+            kdl_string(&window.name),
+            focus
+        ));
+        if window.panes.is_empty() {
+            kdl.push_str("        pane\n");
+        }
+        for pane in &window.panes {
+            if pane.is_empty() {
+                kdl.push_str("        pane\n");
+            } else {
+                kdl.push_str(&format!(
+                    "        pane command=\"sh\" {{\n            args \"-c\" {}\n        }}\n",
+                    kdl_string(pane)
+                ));
+            }
+        }
+        kdl.push_str("    }\n");
+    }
+
+    kdl.push_str("}\n");
+    kdl
+}
+
+/// Parses `zellij list-sessions --no-formatting`, skipping exited sessions.
+fn parse_sessions(output: &str) -> Vec<SessionInfo> {
+    output
+        .lines()
+        .filter(|line| !line.contains("EXITED"))
+        .filter_map(|line| {
+            let name = line.split_whitespace().next()?;
+            if !name.starts_with(SESSION_PREFIX) {
+                return None;
+            }
+            Some(SessionInfo {
+                name: name.to_string(),
+                attached: line.contains("(current)"),
+                windows: None,
+                story_id: session_story_id(name),
+            })
+        })
+        .collect()
+}
+
+impl Multiplexer for Zellij {
+    fn inside_session(&self) -> bool {
+        env::var("ZELLIJ").is_ok()
+    }
+
+    fn session_name(&self, story: &Story) -> String {
+        story_session_name(story)
+    }
+
+    fn create_session(&self, spec: &SessionSpec) -> anyhow::Result<()> {
+        let mut command = Command::new("zellij");
+        command.args(["attach", "--create-background", spec.name, "options"]);
+
+        if !spec.layout.windows.is_empty() {
+            fs::create_dir_all(&self.layout_dir)?;
+            let layout_path = self.layout_dir.join(format!("{}.kdl", spec.name));
+            fs::write(&layout_path, layout_kdl(spec))?;
+            command.arg("--default-layout").arg(layout_path);
+        }
+        if let Some(dir) = spec.dir {
+            command.arg("--default-cwd").arg(dir);
+        }
+
+        // the session's server, and so every pane, inherits our environment
+        command.envs(spec.env.iter().map(|(key, value)| (*key, value.as_str())));
+
+        dbg_file!("Running {:?}", command);
+        error_on_command_fail(&command.output()?, "Zellij")
+    }
+
+    fn attach_session(&self, name: &str) -> anyhow::Result<()> {
+        if self.inside_session() {
+            if env::var("ZELLIJ_SESSION_NAME").is_ok_and(|current| current == name) {
+                return Ok(());
+            }
+            anyhow::bail!(
+                "Zellij can't switch sessions from the command line, detach and run \
+                 `zellij attach {}`",
+                name
+            );
+        }
+
+        // takes over the terminal until the user detaches
+        let status = Command::new("zellij").args(["attach", name]).status()?;
+        if !status.success() {
+            anyhow::bail!("zellij attach failed for {}", name);
+        }
+        Ok(())
+    }
+
+    fn list_sessions(&self) -> anyhow::Result<Vec<SessionInfo>> {
+        let output = Command::new("zellij")
+            .args(["list-sessions", "--no-formatting"])
+            .output()?;
+
+        if !output.status.success()
+            && String::from_utf8_lossy(&output.stderr).contains("No active zellij sessions")
+        {
+            return Ok(Vec::new());
+        }
+        error_on_command_fail(&output, "Zellij")?;
+
+        Ok(parse_sessions(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn kill_session(&self, name: &str) -> anyhow::Result<()> {
+        let output = Command::new("zellij")
+            .args(["delete-session", "--force", name])
+            .output()?;
+        error_on_command_fail(&output, "Zellij")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dummy,
+        multiplexer::{SessionLayout, SessionWindow},
+    };
+
+    #[test]
+    fn writes_a_tab_per_window() {
+        let layout = SessionLayout {
+            windows: vec![
+                SessionWindow {
+                    name: "editor".to_string(),
+                    panes: vec!["nvim \"notes.md\"".to_string()],
+                    layout: None,
+                },
+                SessionWindow {
+                    name: "dev".to_string(),
+                    panes: vec![String::new()],
+                    layout: None,
+                },
+            ],
+        };
+        let spec = SessionSpec {
+            name: "scn--x",
+            story_id: 12,
+            dir: Some(Path::new("/r/api")),
+            layout: &layout,
+            env: &[],
+        };
+
+        let kdl = layout_kdl(&spec);
+        assert!(kdl.contains("    cwd \"/r/api\"\n"));
+        assert!(kdl.contains(
+            "    tab name=\"editor\" focus=true {\n        pane command=\"sh\" {\n            \
+             args \"-c\" \"nvim \\\"notes.md\\\"\"\n        }\n    }\n"
+        ));
+        assert!(kdl.contains("    tab name=\"dev\" {\n        pane\n    }\n"));
+    }
+
+    #[test]
+    fn lists_running_story_sessions() {
+        let mut story = dummy::stories().remove(0);
+        story.name = "SC 7: fix login".to_string();
+        let name = story_session_name(&story);
+
+        let output = format!(
+            "{} [Created 2m ago] (current)\n\
+             scn--sc-7-fix-login [Created 1h ago]\n\
+             scn--old [Created 3d ago] (EXITED - attach to resurrect)\n\
+             misc [Created 1h ago]\n",
+            name
+        );
+        assert_eq!(
+            parse_sessions(&output),
+            vec![
+                SessionInfo {
+                    name,
+                    attached: true,
+                    windows: None,
+                    story_id: Some(story.id),
+                },
+                // from before the ID was in the name
+                SessionInfo {
+                    name: "scn--sc-7-fix-login".to_string(),
+                    attached: false,
+                    windows: None,
+                    story_id: None,
+                },
+            ]
+        );
+    }
+}
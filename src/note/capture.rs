@@ -0,0 +1,160 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::NaiveDateTime;
+
+use crate::{
+    api::{
+        iteration::Iteration,
+        story::{Story, get_story_associated_iteration},
+    },
+    note::{
+        Note,
+        daily::{DAILY_DIR, DailyLink, active_links, build_daily_note},
+    },
+};
+
+/// Where a quick capture is appended to.
+pub enum CaptureTarget<'a> {
+    /// The note of the active story
+    Story {
+        story: &'a Story,
+        iteration_app_url: Option<String>,
+    },
+    /// Today's daily note, with the links a new daily note starts with
+    Daily { links: Vec<DailyLink> },
+}
+
+impl<'a> CaptureTarget<'a> {
+    /// The active story's note, or today's daily note when no story is active.
+    pub fn resolve(
+        active_story: Option<&'a Story>,
+        stories: &[Story],
+        current_iterations: &[Iteration],
+    ) -> Self {
+        match active_story {
+            Some(story) => CaptureTarget::Story {
+                story,
+                iteration_app_url: get_story_associated_iteration(
+                    story.iteration_id,
+                    current_iterations,
+                )
+                .map(|it| it.app_url.clone()),
+            },
+            None => CaptureTarget::Daily {
+                links: active_links(stories, current_iterations),
+            },
+        }
+    }
+}
+
+/// Formats captured text as a timestamped bullet. Daily notes are already dated, so
+/// they only get the time.
+pub fn capture_line(text: &str, now: NaiveDateTime, is_daily: bool) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let stamp = if is_daily {
+        now.format("%H:%M")
+    } else {
+        now.format("%Y-%m-%d %H:%M")
+    };
+    format!("- {} {}", stamp, text)
+}
+
+/// Appends `line` to the note at `path`, creating the note from `initial` first when it
+/// is missing or empty.
+fn append_line(
+    path: &Path,
+    line: &str,
+    initial: impl FnOnce() -> anyhow::Result<String>,
+) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut contents = fs::read_to_string(path).unwrap_or_default();
+    if contents.is_empty() {
+        contents = initial()?;
+        fs::write(path, &contents)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let separator = if contents.ends_with('\n') { "" } else { "\n" };
+    writeln!(file, "{}{}", separator, line)
+        .with_context(|| format!("Failed to write to {}", path.display()))
+}
+
+/// Appends `text` as a timestamped bullet to `target`, creating the note and its
+/// frontmatter if needed. Returns the path of the note.
+pub fn capture(
+    notes_dir: &Path,
+    target: CaptureTarget,
+    text: &str,
+    now: NaiveDateTime,
+) -> anyhow::Result<PathBuf> {
+    if text.trim().is_empty() {
+        anyhow::bail!("Nothing to capture");
+    }
+
+    match target {
+        CaptureTarget::Story {
+            story,
+            iteration_app_url,
+        } => {
+            let note = Note::new(
+                &notes_dir,
+                story.id,
+                story.name.clone(),
+                story.app_url.clone(),
+                iteration_app_url,
+            );
+            append_line(&note.path, &capture_line(text, now, false), || {
+                Ok(format!("---\n{}---\n", note.frontmatter.to_yaml_string()?))
+            })?;
+            Ok(note.path)
+        }
+
+        CaptureTarget::Daily { links } => {
+            let date = now.date();
+            let path = notes_dir.join(DAILY_DIR).join(format!("{}.md", date));
+            append_line(&path, &capture_line(text, now, true), || {
+                Ok(build_daily_note(&path, date, &links))
+            })?;
+            Ok(path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn capture_creates_daily_note_and_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = NaiveDate::from_ymd_opt(2026, 3, 4)
+            .unwrap()
+            .and_hms_opt(9, 5, 0)
+            .unwrap();
+        let daily = || CaptureTarget::Daily { links: Vec::new() };
+
+        let path = capture(dir.path(), daily(), "first\nthought", now).unwrap();
+        capture(dir.path(), daily(), "second", now).unwrap();
+
+        assert_eq!(path, dir.path().join("daily/2026-03-04.md"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "---\nschema_version: 1\ncreated: 2026-03-04\ntype: daily\ntags: []\n---\n\
+             - 09:05 first thought\n- 09:05 second\n"
+        );
+    }
+}
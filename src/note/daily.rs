@@ -0,0 +1,184 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use slugify::slugify;
+
+use crate::{
+    api::{iteration::Iteration, story::Story},
+    note::{frontmatter::SCHEMA_VERSION, todo::parse_checkbox},
+};
+
+pub const DAILY_DIR: &str = "daily";
+
+const ACTIVE_HEADING: &str = "## Active";
+const CARRIED_HEADING: &str = "## Carried over";
+
+/// A wikilink to another note, relative to the notes directory and without extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyLink {
+    pub target: String,
+    pub label: String,
+}
+
+impl DailyLink {
+    fn to_markdown(&self) -> String {
+        format!("- [[{}|{}]]", self.target, self.label)
+    }
+}
+
+/// Returns the date a daily note is for, if `path` is named like `2026-02-18.md`.
+pub fn daily_note_date(path: &Path) -> Option<NaiveDate> {
+    let stem = path.file_stem()?.to_str()?;
+    NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok()
+}
+
+/// Finds the most recent daily note in `daily_dir` dated strictly before `date`.
+pub fn previous_daily_note(daily_dir: &Path, date: NaiveDate) -> Option<PathBuf> {
+    std::fs::read_dir(daily_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("md"))
+        .filter_map(|path| daily_note_date(&path).map(|d| (d, path)))
+        .filter(|(d, _)| *d < date)
+        .max_by_key(|(d, _)| *d)
+        .map(|(_, path)| path)
+}
+
+/// Returns every unchecked checkbox line in `contents`, keeping its indentation.
+pub fn unchecked_items(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter(|line| parse_checkbox(line).is_some_and(|(checked, _)| !checked))
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+/// Returns the text of every ticked checkbox in `contents`, without the checkbox.
+pub fn checked_items(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(parse_checkbox)
+        .filter(|(checked, text)| *checked && !text.is_empty())
+        .map(|(_, text)| text.to_string())
+        .collect()
+}
+
+/// Returns the text of every unticked checkbox in `contents`, without the checkbox.
+pub fn unchecked_texts(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(parse_checkbox)
+        .filter(|(checked, text)| !*checked && !text.is_empty())
+        .map(|(_, text)| text.to_string())
+        .collect()
+}
+
+/// Links to the notes of in-progress stories and the current iterations.
+pub fn active_links(stories: &[Story], current_iterations: &[Iteration]) -> Vec<DailyLink> {
+    let story_links = stories
+        .iter()
+        .filter(|s| s.started && !s.completed)
+        .map(|s| DailyLink {
+            target: format!("stories/{}", slugify!(&s.name)),
+            label: format!("sc-{} {}", s.id, s.name),
+        });
+
+    let iteration_links = current_iterations.iter().map(|it| DailyLink {
+        target: format!("iterations/{}", slugify!(&it.name)),
+        label: it.name.clone(),
+    });
+
+    story_links.chain(iteration_links).collect()
+}
+
+/// Builds the contents of a new daily note, carrying over unchecked items from the
+/// previous daily note in the same directory.
+pub fn build_daily_note(path: &Path, date: NaiveDate, links: &[DailyLink]) -> String {
+    let carried = path
+        .parent()
+        .and_then(|dir| previous_daily_note(dir, date))
+        .and_then(|prev| read_to_string(prev).ok())
+        .map(|contents| unchecked_items(&contents))
+        .unwrap_or_default();
+
+    render_daily_note(date, links, &carried)
+}
+
+fn render_daily_note(date: NaiveDate, links: &[DailyLink], carried: &[String]) -> String {
+    let mut out = format!(
+        "---\nschema_version: {}\ncreated: {}\ntype: daily\ntags: []\n---\n",
+        SCHEMA_VERSION, date
+    );
+
+    if !links.is_empty() {
+        out.push_str(&format!("\n{}\n\n", ACTIVE_HEADING));
+        for link in links {
+            out.push_str(&link.to_markdown());
+            out.push('\n');
+        }
+    }
+
+    if !carried.is_empty() {
+        out.push_str(&format!("\n{}\n\n", CARRIED_HEADING));
+        for item in carried {
+            out.push_str(item);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchecked_items_keeps_only_open_checkboxes() {
+        let contents = "---\ntype: daily\n---\n- [ ] one\n- [x] done\n  - [ ] nested\n* [ ] star\ntext\n";
+        assert_eq!(
+            unchecked_items(contents),
+            vec!["- [ ] one", "  - [ ] nested", "* [ ] star"]
+        );
+    }
+
+    #[test]
+    fn daily_note_date_parses_stem() {
+        let date = daily_note_date(Path::new("/notes/daily/2026-02-18.md"));
+        assert_eq!(date, NaiveDate::from_ymd_opt(2026, 2, 18));
+        assert_eq!(daily_note_date(Path::new("/notes/daily/todo.md")), None);
+    }
+
+    #[test]
+    fn previous_daily_note_skips_today_and_future() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["2026-02-16.md", "2026-02-17.md", "2026-02-18.md", "2026-02-19.md"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+        let prev = previous_daily_note(dir.path(), today).unwrap();
+        assert_eq!(prev.file_name().unwrap(), "2026-02-17.md");
+    }
+
+    #[test]
+    fn render_includes_links_and_carried_items() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+        let links = vec![DailyLink {
+            target: "stories/fix-bug".to_string(),
+            label: "sc-1 Fix bug".to_string(),
+        }];
+        let carried = vec!["- [ ] follow up".to_string()];
+
+        let note = render_daily_note(date, &links, &carried);
+        assert!(note.starts_with(
+            "---\nschema_version: 1\ncreated: 2026-02-18\ntype: daily\ntags: []\n---\n"
+        ));
+        assert!(note.contains("- [[stories/fix-bug|sc-1 Fix bug]]"));
+        assert!(note.contains("## Carried over\n\n- [ ] follow up\n"));
+    }
+}
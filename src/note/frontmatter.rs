@@ -2,6 +2,10 @@ use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use slugify::slugify;
 
+/// Version of the frontmatter written to new notes. Bump it and add a step to
+/// `note::migrate` when the frontmatter changes, so `notes migrate` can update old notes.
+pub const SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub enum NoteType {
     Meeting,
@@ -16,17 +20,17 @@ pub struct Frontmatter {
     #[serde(skip_serializing, skip_deserializing)]
     pub slug_id: String,
 
+    schema_version: u32,
+
     /// Story fields
     story_id: String, // e.g. sc-12345
     story_link: String,
     story_name: String,
 
     /// Iteration fields
-    #[serde(rename = "iteration")]
     iteration_link: Option<String>,
 
     /// Epic fields
-    #[serde(rename = "epic")]
     epic_link: Option<String>,
 
     /// Note fields
@@ -52,6 +56,8 @@ impl Frontmatter {
         Self {
             slug_id: slug,
 
+            schema_version: SCHEMA_VERSION,
+
             story_id: format!("sc-{}", story_id),
             story_name,
             story_link: story_app_url,
@@ -71,3 +77,130 @@ impl Frontmatter {
         Ok(serde_yaml::to_string(self)?)
     }
 }
+
+/// Loosely typed view over any note's frontmatter. Story, iteration, epic, daily and
+/// scratch notes all use different keys, so every field is optional.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct NoteMeta {
+    pub story_id: Option<String>,
+    pub story_name: Option<String>,
+    pub iteration_id: Option<String>,
+    pub iteration_name: Option<String>,
+    pub epic_name: Option<String>,
+    pub name: Option<String>,
+    pub created: Option<NaiveDate>,
+    #[serde(rename = "type")]
+    pub note_type: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub schema_version: Option<u32>,
+}
+
+impl NoteMeta {
+    /// Parses the frontmatter at the top of `contents`, falling back to an empty
+    /// `NoteMeta` when there is none or it isn't valid YAML.
+    pub fn parse(contents: &str) -> NoteMeta {
+        split_frontmatter(contents)
+            .and_then(|(yaml, _)| serde_yaml::from_str(yaml).ok())
+            .unwrap_or_default()
+    }
+
+    /// The story id as a number, from `sc-12345`.
+    pub fn story_number(&self) -> Option<i32> {
+        self.story_id.as_deref()?.strip_prefix("sc-")?.parse().ok()
+    }
+
+    /// The iteration id as a number, from `it-123`.
+    pub fn iteration_number(&self) -> Option<i32> {
+        self.iteration_id.as_deref()?.strip_prefix("it-")?.parse().ok()
+    }
+
+    /// A human readable title for the note, if the frontmatter has one.
+    pub fn title(&self) -> Option<String> {
+        if let Some(name) = &self.story_name {
+            return Some(match &self.story_id {
+                Some(id) => format!("{} {}", id, name),
+                None => name.clone(),
+            });
+        }
+
+        self.iteration_name
+            .clone()
+            .or_else(|| self.epic_name.clone())
+            .or_else(|| self.name.clone())
+    }
+}
+
+/// Splits `contents` into its YAML frontmatter and the body that follows it.
+pub fn split_frontmatter(contents: &str) -> Option<(&str, &str)> {
+    let rest = contents.strip_prefix("---")?;
+    let rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n'))?;
+
+    // the closing fence may be the very first line when the frontmatter is empty
+    let (yaml, after) = if let Some(after) = rest.strip_prefix("---") {
+        ("", after)
+    } else {
+        let end = rest.find("\n---")?;
+        (&rest[..end + 1], &rest[end + 4..])
+    };
+
+    let body = after
+        .strip_prefix("\r\n")
+        .or_else(|| after.strip_prefix('\n'))
+        .unwrap_or(after);
+
+    Some((yaml, body))
+}
+
+/// `value` as a YAML scalar, quoted when it would otherwise be read as something else,
+/// e.g. `Fix: login` or `C# notes`.
+pub fn yaml_string(value: &str) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{:?}", value))
+}
+
+/// Sets `key: value` in the frontmatter of `contents`, replacing an existing entry for
+/// `key` or adding one before the closing fence. Notes without frontmatter get one.
+/// `value` is written as is, plain text should go through `yaml_string` first.
+pub fn set_frontmatter_field(contents: &str, key: &str, value: &str) -> String {
+    let entry = format!("{}: {}", key, value);
+
+    let Some((yaml, body)) = split_frontmatter(contents) else {
+        return format!("---\n{}\n---\n{}", entry, contents);
+    };
+
+    let prefix = format!("{}:", key);
+    let mut replaced = false;
+    let mut in_old_value = false;
+    let mut lines: Vec<String> = Vec::new();
+    for line in yaml.lines() {
+        // drop the block list or indented value that belonged to the replaced key
+        if in_old_value && (line.starts_with([' ', '\t']) || line.starts_with("- ")) {
+            continue;
+        }
+        in_old_value = false;
+
+        if !replaced && line.starts_with(&prefix) {
+            replaced = true;
+            in_old_value = true;
+            lines.push(entry.clone());
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+
+    if !replaced {
+        lines.push(entry);
+    }
+
+    format!("---\n{}\n---\n{}", lines.join("\n"), body)
+}
+
+/// Number of lines taken up by the frontmatter, including both fences.
+pub fn frontmatter_line_count(contents: &str) -> usize {
+    match split_frontmatter(contents) {
+        Some((_, body)) => contents[..contents.len() - body.len()].lines().count(),
+        None => 0,
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::note::{frontmatter::frontmatter_line_count, search::SearchIndex};
+
+/// A `[[wikilink]]` found in a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteLink {
+    /// the raw link target, without any `#heading` or `|alias`
+    pub target: String,
+    pub label: String,
+    /// the note the link resolves to, if it exists
+    pub path: Option<PathBuf>,
+}
+
+/// Outgoing links, backlinks and story mentions across every note in the notes directory.
+#[derive(Clone, Default)]
+pub struct LinkGraph {
+    outgoing: HashMap<PathBuf, Vec<NoteLink>>,
+    backlinks: HashMap<PathBuf, Vec<PathBuf>>,
+    story_mentions: HashMap<i32, Vec<PathBuf>>,
+    story_notes: HashMap<i32, PathBuf>,
+    titles: HashMap<PathBuf, String>,
+}
+
+impl fmt::Debug for LinkGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkGraph")
+            .field("notes", &self.outgoing.len())
+            .field("stories", &self.story_mentions.len())
+            .finish()
+    }
+}
+
+/// Parses every `[[target]]`, `[[target|alias]]` and `[[target#heading]]` in `line`,
+/// returning `(target, label)` pairs.
+pub fn parse_wikilinks(line: &str) -> Vec<(String, String)> {
+    let mut links = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+
+        let inner = &after[..end];
+        let (target, alias) = match inner.split_once('|') {
+            Some((target, alias)) => (target, Some(alias)),
+            None => (inner, None),
+        };
+        let target = target.split('#').next().unwrap_or(target).trim();
+
+        if !target.is_empty() {
+            let label = alias.unwrap_or(target).trim().to_string();
+            links.push((target.to_string(), label));
+        }
+
+        rest = &after[end + 2..];
+    }
+
+    links
+}
+
+/// Parses every `sc-12345` story reference in `line`.
+pub fn parse_story_mentions(line: &str) -> Vec<i32> {
+    let lowered = line.to_ascii_lowercase();
+    let bytes = lowered.as_bytes();
+    let mut ids = Vec::new();
+
+    for (idx, _) in lowered.match_indices("sc-") {
+        let at_word_start = idx == 0 || !bytes[idx - 1].is_ascii_alphanumeric();
+        if !at_word_start {
+            continue;
+        }
+
+        let digits: String = lowered[idx + 3..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+
+        if let Ok(id) = digits.parse() {
+            ids.push(id);
+        }
+    }
+
+    ids
+}
+
+/// Resolves a wikilink target the way Obsidian does: targets with a path are relative
+/// to the notes directory, bare names match any note with that file name.
+fn resolve_target(
+    target: &str,
+    notes_dir: &Path,
+    by_stem: &HashMap<String, PathBuf>,
+) -> Option<PathBuf> {
+    let with_ext = if target.ends_with(".md") {
+        target.to_string()
+    } else {
+        format!("{}.md", target)
+    };
+
+    if target.contains('/') {
+        let path = notes_dir.join(with_ext);
+        return path.is_file().then_some(path);
+    }
+
+    by_stem.get(&target.to_lowercase()).cloned()
+}
+
+impl LinkGraph {
+    pub fn build(index: &SearchIndex, notes_dir: &Path) -> LinkGraph {
+        let mut graph = LinkGraph::default();
+
+        // sorted so bare-name resolution is deterministic when file names collide
+        let mut paths: Vec<&PathBuf> = index.notes.keys().collect();
+        paths.sort();
+
+        let mut by_stem = HashMap::new();
+        for path in &paths {
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                by_stem
+                    .entry(stem.to_lowercase())
+                    .or_insert_with(|| (*path).clone());
+            }
+        }
+
+        for path in paths {
+            let note = &index.notes[path];
+            graph.titles.insert(path.clone(), note.title.clone());
+            if let Some(id) = note.story_id {
+                graph.story_notes.insert(id, path.clone());
+            }
+
+            let mut outgoing: Vec<NoteLink> = Vec::new();
+            let skip = frontmatter_line_count(&note.lines.join("\n"));
+            for line in note.lines.iter().skip(skip) {
+                for (target, label) in parse_wikilinks(line) {
+                    if outgoing.iter().any(|l| l.target == target) {
+                        continue;
+                    }
+                    let resolved = resolve_target(&target, notes_dir, &by_stem);
+                    outgoing.push(NoteLink {
+                        target,
+                        label,
+                        path: resolved,
+                    });
+                }
+
+                for id in parse_story_mentions(line) {
+                    if note.story_id == Some(id) {
+                        continue;
+                    }
+                    let mentions = graph.story_mentions.entry(id).or_default();
+                    if !mentions.contains(path) {
+                        mentions.push(path.clone());
+                    }
+                }
+            }
+
+            for link in &outgoing {
+                if let Some(target) = &link.path {
+                    let sources = graph.backlinks.entry(target.clone()).or_default();
+                    if !sources.contains(path) {
+                        sources.push(path.clone());
+                    }
+                }
+            }
+
+            graph.outgoing.insert(path.clone(), outgoing);
+        }
+
+        graph
+    }
+
+    pub fn outgoing(&self, path: &Path) -> &[NoteLink] {
+        self.outgoing.get(path).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Notes linking to `path`. For story notes this includes every note mentioning
+    /// the story by its `sc-` id.
+    pub fn backlinks(&self, path: &Path) -> Vec<&PathBuf> {
+        let mut sources: Vec<&PathBuf> = self
+            .backlinks
+            .get(path)
+            .map(|v| v.iter().collect())
+            .unwrap_or_default();
+
+        let story_id = self
+            .story_notes
+            .iter()
+            .find(|(_, note)| note.as_path() == path)
+            .map(|(id, _)| *id);
+
+        if let Some(id) = story_id {
+            for mention in self.story_mentions(id) {
+                if !sources.contains(&mention) {
+                    sources.push(mention);
+                }
+            }
+        }
+
+        sources
+    }
+
+    /// Notes that mention the story `sc-<id>`, excluding the story's own note.
+    pub fn story_mentions(&self, id: i32) -> &[PathBuf] {
+        self.story_mentions
+            .get(&id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Notes referring to a story, either through its `sc-` id or by linking to its note.
+    pub fn story_backlinks(&self, id: i32) -> Vec<&PathBuf> {
+        match self.story_notes.get(&id) {
+            Some(note) => self.backlinks(note),
+            None => self.story_mentions(id).iter().collect(),
+        }
+    }
+
+    pub fn title(&self, path: &Path) -> String {
+        self.titles
+            .get(path)
+            .cloned()
+            .or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::note::search::IndexedNote;
+
+    #[test]
+    fn parse_wikilinks_handles_alias_and_heading() {
+        let links = parse_wikilinks("see [[stories/foo|Foo]] and [[bar#Intro]] [[unclosed");
+
+        assert_eq!(
+            links,
+            vec![
+                ("stories/foo".to_string(), "Foo".to_string()),
+                ("bar".to_string(), "bar".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_story_mentions_requires_word_boundary() {
+        assert_eq!(
+            parse_story_mentions("SC-12 and sc-345, not misc-6"),
+            vec![12, 345]
+        );
+    }
+
+    #[test]
+    fn build_links_backlinks_and_mentions() {
+        let note = |lines: &[&str], story_id| IndexedNote {
+            mtime: 0,
+            title: String::new(),
+            tags: vec![],
+            story_id,
+            lines: lines.iter().map(|l| l.to_string()).collect(),
+        };
+
+        let mut index = SearchIndex::default();
+        let story = PathBuf::from("/notes/stories/foo.md");
+        let daily = PathBuf::from("/notes/daily/2026-02-18.md");
+        index.notes.insert(
+            story.clone(),
+            note(&["---", "story_id: sc-1", "---"], Some(1)),
+        );
+        index
+            .notes
+            .insert(daily.clone(), note(&["- [[foo]] worked on sc-1"], None));
+
+        let graph = LinkGraph::build(&index, Path::new("/notes"));
+
+        assert_eq!(graph.outgoing(&daily)[0].path, Some(story.clone()));
+        assert_eq!(graph.backlinks(&story), vec![&daily]);
+        assert_eq!(graph.story_mentions(1), &[daily]);
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use slugify::slugify;
+
+use crate::{
+    api::{iteration::Iteration, story::Story},
+    note::{
+        frontmatter::{NoteMeta, set_frontmatter_field, yaml_string},
+        report::is_report,
+        todo::collect_markdown_files,
+    },
+};
+
+/// Notes of finished stories and iterations are moved under this directory, keeping
+/// their category folder, e.g. `archive/stories/foo.md`.
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// Note categories that notes can be moved between, in the order the Notes view shows them.
+pub const CATEGORIES: [&str; 5] = ["daily", "stories", "iterations", "epics", "scratch"];
+
+/// How long a deleted note can be restored for before it is removed for good.
+pub const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+/// Deleted notes are parked here, under the cache directory, during the undo window.
+pub const TRASH_DIR: &str = "trash";
+
+/// A deleted note, parked in the trash directory until the undo window closes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedNote {
+    pub original: PathBuf,
+    pub trashed: PathBuf,
+}
+
+impl TrashedNote {
+    /// Moves the note back to where it was deleted from.
+    pub fn restore(&self) -> anyhow::Result<()> {
+        if self.original.exists() {
+            anyhow::bail!("{} already exists", self.original.display());
+        }
+        move_file(&self.trashed, &self.original)
+    }
+
+    /// Permanently removes the trashed copy.
+    pub fn purge(&self) -> anyhow::Result<()> {
+        fs::remove_file(&self.trashed)
+            .with_context(|| format!("Failed to remove {}", self.trashed.display()))
+    }
+}
+
+/// The target used to link to `path` from other notes: its path relative to the notes
+/// directory without the `.md` extension, e.g. `stories/foo`.
+fn link_target(notes_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(notes_dir).ok()?.with_extension("");
+    let parts: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(parts.join("/"))
+}
+
+/// Rewrites every `[[old]]`, `[[old|alias]]` and `[[old#heading]]` in `contents` to point
+/// at `new`. Returns `None` when nothing changed.
+pub fn rewrite_links(contents: &str, old: &str, new: &str) -> Option<String> {
+    let mut out = String::with_capacity(contents.len());
+    let mut rest = contents;
+    let mut changed = false;
+
+    while let Some(start) = rest.find("[[") {
+        out.push_str(&rest[..start + 2]);
+        rest = &rest[start + 2..];
+
+        let Some(end) = rest.find("]]") else {
+            break;
+        };
+
+        let inner = &rest[..end];
+        let target_len = inner.find(['|', '#']).unwrap_or(inner.len());
+        let target = &inner[..target_len];
+        let bare = target.strip_suffix(".md").unwrap_or(target);
+
+        if bare.trim() == old {
+            out.push_str(new);
+            changed = true;
+        } else {
+            out.push_str(target);
+        }
+        out.push_str(&inner[target_len..]);
+        rest = &rest[end..];
+    }
+
+    out.push_str(rest);
+    changed.then_some(out)
+}
+
+/// Renames a file, falling back to copy and delete when `to` is on another filesystem.
+fn move_file(from: &Path, to: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)
+            .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+        fs::remove_file(from)?;
+    }
+
+    Ok(())
+}
+
+/// Moves a note to `to` and points every inbound wikilink at its new location.
+/// Returns the number of notes whose links were updated.
+fn relocate_note(notes_dir: &Path, from: &Path, to: &Path) -> anyhow::Result<usize> {
+    if to.exists() {
+        anyhow::bail!("{} already exists", to.display());
+    }
+
+    let old_target = link_target(notes_dir, from);
+    let new_target = link_target(notes_dir, to);
+    let old_stem = from.file_stem().map(|s| s.to_string_lossy().to_string());
+    let new_stem = to.file_stem().map(|s| s.to_string_lossy().to_string());
+
+    move_file(from, to)?;
+
+    let mut updated = 0;
+    for note in collect_markdown_files(notes_dir) {
+        let Ok(contents) = fs::read_to_string(&note) else {
+            continue;
+        };
+
+        let mut rewritten = None;
+        if let (Some(old), Some(new)) = (&old_target, &new_target) {
+            rewritten = rewrite_links(&contents, old, new);
+        }
+        if old_stem != new_stem
+            && let (Some(old), Some(new)) = (&old_stem, &new_stem)
+        {
+            let current = rewritten.as_deref().unwrap_or(&contents);
+            rewritten = rewrite_links(current, old, new).or(rewritten);
+        }
+
+        if let Some(rewritten) = rewritten {
+            fs::write(&note, rewritten)
+                .with_context(|| format!("Failed to update links in {}", note.display()))?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Renames a note to the slug of `name`, records `name` in its frontmatter and updates
+/// inbound wikilinks. Returns the note's new path.
+pub fn rename_note(notes_dir: &Path, path: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    let slug = slugify!(name);
+    if slug.is_empty() {
+        anyhow::bail!("Note name can't be empty");
+    }
+
+    let new_path = path.with_file_name(format!("{}.md", slug));
+    if new_path != path && new_path.exists() {
+        anyhow::bail!("{} already exists", new_path.display());
+    }
+
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    fs::write(path, set_frontmatter_field(&contents, "name", &yaml_string(name)))?;
+
+    if new_path != path {
+        relocate_note(notes_dir, path, &new_path)?;
+    }
+
+    Ok(new_path)
+}
+
+/// Moves a note into another category folder, e.g. from `scratch/` to `stories/`.
+pub fn move_note(notes_dir: &Path, path: &Path, category: &str) -> anyhow::Result<PathBuf> {
+    let file_name = path.file_name().context("Note has no file name")?;
+    let new_path = notes_dir.join(category).join(file_name);
+    if new_path != path {
+        relocate_note(notes_dir, path, &new_path)?;
+    }
+
+    Ok(new_path)
+}
+
+/// Moves a note into the archive tree, keeping its path relative to the notes directory.
+pub fn archive_note(notes_dir: &Path, path: &Path) -> anyhow::Result<PathBuf> {
+    let relative = path
+        .strip_prefix(notes_dir)
+        .with_context(|| format!("{} is not in the notes directory", path.display()))?;
+
+    if relative.starts_with(ARCHIVE_DIR) {
+        anyhow::bail!("{} is already archived", path.display());
+    }
+
+    let new_path = notes_dir.join(ARCHIVE_DIR).join(relative);
+    relocate_note(notes_dir, path, &new_path)?;
+    Ok(new_path)
+}
+
+/// Moves a note into `trash_dir` so it can be restored until the undo window closes.
+pub fn trash_note(path: &Path, trash_dir: &Path) -> anyhow::Result<TrashedNote> {
+    let file_name = path
+        .file_name()
+        .context("Note has no file name")?
+        .to_string_lossy();
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+
+    let trashed = trash_dir.join(format!("{}-{}", stamp, file_name));
+    move_file(path, &trashed)?;
+
+    Ok(TrashedNote {
+        original: path.to_path_buf(),
+        trashed,
+    })
+}
+
+/// Removes notes trashed more than `UNDO_WINDOW` ago, which are left behind when the app
+/// exits before their undo window closes. Returns how many were removed.
+pub fn purge_stale_trash(trash_dir: &Path) -> anyhow::Result<usize> {
+    let Ok(entries) = fs::read_dir(trash_dir) else {
+        return Ok(0);
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+
+    let mut purged = 0;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        // named `<millis>-<file name>` by `trash_note`, unknown files count as stale
+        let trashed_at = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.split_once('-'))
+            .and_then(|(stamp, _)| stamp.parse::<u128>().ok())
+            .unwrap_or_default();
+        if now.saturating_sub(trashed_at) < UNDO_WINDOW.as_millis() || !path.is_file() {
+            continue;
+        }
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+        purged += 1;
+    }
+
+    Ok(purged)
+}
+
+/// Story notes of completed stories and iteration notes of iterations that ended before
+/// `today`. Only stories and iterations we have data for are considered.
+pub fn finished_notes(
+    notes_dir: &Path,
+    stories: &[Story],
+    iterations: &[Iteration],
+    today: NaiveDate,
+) -> Vec<PathBuf> {
+    let meta = |path: &Path| {
+        fs::read_to_string(path)
+            .map(|contents| NoteMeta::parse(&contents))
+            .unwrap_or_default()
+    };
+
+    let finished_stories = collect_markdown_files(&notes_dir.join("stories"))
+        .into_iter()
+        .filter(|path| {
+            meta(path)
+                .story_number()
+                .and_then(|id| stories.iter().find(|s| s.id == id))
+                .is_some_and(|s| s.completed)
+        });
+
+    let ended_iterations = collect_markdown_files(&notes_dir.join("iterations"))
+        .into_iter()
+        .filter(|path| !is_report(path))
+        .filter(|path| {
+            meta(path)
+                .iteration_number()
+                .and_then(|id| iterations.iter().find(|it| it.id == id))
+                .is_some_and(|it| it.end_date < today)
+        });
+
+    finished_stories.chain(ended_iterations).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_links_keeps_alias_and_heading() {
+        let contents = "[[foo]] [[foo|Foo]] [[foo#Intro]] [[foobar]] [[foo.md]]";
+        assert_eq!(
+            rewrite_links(contents, "foo", "bar").as_deref(),
+            Some("[[bar]] [[bar|Foo]] [[bar#Intro]] [[foobar]] [[bar]]")
+        );
+        assert_eq!(rewrite_links(contents, "baz", "bar"), None);
+    }
+
+    #[test]
+    fn rename_updates_frontmatter_and_inbound_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes_dir = dir.path();
+        fs::create_dir_all(notes_dir.join("scratch")).unwrap();
+        fs::create_dir_all(notes_dir.join("daily")).unwrap();
+
+        let note = notes_dir.join("scratch/old-idea.md");
+        let daily = notes_dir.join("daily/2026-02-18.md");
+        fs::write(&note, "---\nname: Old idea\n---\nbody\n").unwrap();
+        fs::write(&daily, "- [[old-idea]] and [[scratch/old-idea|it]]\n").unwrap();
+
+        let renamed = rename_note(notes_dir, &note, "New idea").unwrap();
+
+        assert_eq!(renamed, notes_dir.join("scratch/new-idea.md"));
+        assert!(!note.exists());
+        assert_eq!(
+            fs::read_to_string(&renamed).unwrap(),
+            "---\nname: New idea\n---\nbody\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&daily).unwrap(),
+            "- [[new-idea]] and [[scratch/new-idea|it]]\n"
+        );
+    }
+
+    #[test]
+    fn rename_quotes_names_that_are_not_plain_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes_dir = dir.path();
+        fs::create_dir_all(notes_dir.join("stories")).unwrap();
+        let note = notes_dir.join("stories/login.md");
+        let contents = "---\nname: Login\nstory_id: sc-12\ntags: [auth]\n---\nbody\n";
+        fs::write(&note, contents).unwrap();
+
+        let renamed = rename_note(notes_dir, &note, "Fix: login").unwrap();
+        let renamed = rename_note(notes_dir, &renamed, "C# notes").unwrap();
+
+        let meta = NoteMeta::parse(&fs::read_to_string(&renamed).unwrap());
+        assert_eq!(meta.title().as_deref(), Some("C# notes"));
+        assert_eq!(meta.story_number(), Some(12));
+        assert_eq!(meta.tags, vec!["auth".to_string()]);
+    }
+
+    #[test]
+    fn trashed_note_can_be_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("scratch/idea.md");
+        fs::create_dir_all(note.parent().unwrap()).unwrap();
+        fs::write(&note, "idea").unwrap();
+
+        let trashed = trash_note(&note, &dir.path().join("trash")).unwrap();
+        assert!(!note.exists());
+
+        trashed.restore().unwrap();
+        assert_eq!(fs::read_to_string(&note).unwrap(), "idea");
+    }
+
+    #[test]
+    fn purges_trash_left_from_a_previous_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let trash_dir = dir.path().join(TRASH_DIR);
+        fs::create_dir_all(&trash_dir).unwrap();
+        fs::write(trash_dir.join("1700000000000-old.md"), "old").unwrap();
+
+        let note = dir.path().join("new.md");
+        fs::write(&note, "new").unwrap();
+        let trashed = trash_note(&note, &trash_dir).unwrap();
+
+        assert_eq!(purge_stale_trash(&trash_dir).unwrap(), 1);
+        assert!(trashed.trashed.exists());
+        assert_eq!(purge_stale_trash(&dir.path().join("missing")).unwrap(), 0);
+    }
+
+    #[test]
+    fn finished_notes_skips_iteration_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let iteration = crate::dummy::iteration();
+        let today = iteration.end_date + chrono::Duration::days(1);
+
+        let note = dir.path().join("iterations/sprint-42.md");
+        fs::create_dir_all(note.parent().unwrap()).unwrap();
+        fs::write(&note, "---\niteration_id: it--1\n---\n").unwrap();
+        fs::write(
+            dir.path().join("iterations/sprint-42-report.md"),
+            "---\niteration_id: it--1\ntype: report\n---\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            finished_notes(dir.path(), &[], &[iteration], today),
+            vec![note]
+        );
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::note::{
+    frontmatter::{NoteMeta, SCHEMA_VERSION, set_frontmatter_field, split_frontmatter},
+    manage::ARCHIVE_DIR,
+    todo::collect_markdown_files,
+};
+
+/// Rewrites frontmatter from one schema version to the next. Takes the note contents and
+/// its category folder, e.g. `stories`.
+type Step = fn(&str, Option<&str>) -> String;
+
+/// Indexed by the version being migrated from.
+const STEPS: [Step; SCHEMA_VERSION as usize] = [v0_to_v1];
+
+/// Lines of context around each change in a dry-run diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// `value` as an iteration or epic ID with its prefix, e.g. `4`, `"4"` or `IT-4` become
+/// `it-4`. `None` when it isn't an ID, e.g. a link.
+fn canonical_id(value: &str, prefix: &str) -> Option<String> {
+    let value = value.trim().trim_matches(['"', '\'']).to_lowercase();
+    let number = value
+        .strip_prefix(prefix)
+        .map_or(value.as_str(), |rest| rest.trim_start_matches('-'));
+    number.parse::<i32>().ok().map(|n| format!("{}-{}", prefix, n))
+}
+
+/// Puts iteration and epic references under the keys iteration and epic notes use:
+/// `iteration_id: it-…` and `epic_id: ep-…` for IDs, `iteration_link` and `epic_link` for
+/// links. Story notes used to write their links under `iteration` and `epic`.
+fn normalize_entity_keys(contents: &str) -> String {
+    let Some((yaml, body)) = split_frontmatter(contents) else {
+        return contents.to_string();
+    };
+
+    let mut lines = Vec::new();
+    let mut changed = false;
+    for line in yaml.lines() {
+        let entry = line.split_once(':').and_then(|(key, value)| {
+            let (kind, prefix) = match key {
+                "iteration" | "iteration_id" => ("iteration", "it"),
+                "epic" | "epic_id" => ("epic", "ep"),
+                _ => return None,
+            };
+            match canonical_id(value, prefix) {
+                Some(id) => Some(format!("{}_id: {}", kind, id)),
+                // a link, or an ID we can't make sense of, which is left alone
+                None if key == kind => Some(format!("{}_link:{}", kind, value)),
+                None => None,
+            }
+        });
+        changed |= entry.as_deref().is_some_and(|entry| entry != line);
+        lines.push(entry.unwrap_or_else(|| line.to_string()));
+    }
+    if !changed {
+        return contents.to_string();
+    }
+
+    format!("---\n{}\n---\n{}", lines.join("\n"), body)
+}
+
+/// v1 adds `schema_version`, makes sure every note has a `type` and `tags`, and keys
+/// iteration and epic references the same way in every kind of note.
+fn v0_to_v1(contents: &str, category: Option<&str>) -> String {
+    let contents = &normalize_entity_keys(contents);
+    let meta = NoteMeta::parse(contents);
+    let yaml = split_frontmatter(contents).map_or("", |(yaml, _)| yaml);
+    let has_key = |key: &str| yaml.lines().any(|l| l.starts_with(&format!("{}:", key)));
+
+    let mut out = contents.to_string();
+
+    if meta.note_type.is_none() {
+        let note_type = if meta.story_id.is_some() {
+            Some("General")
+        } else if meta.iteration_id.is_some() {
+            Some("iteration")
+        } else if has_key("epic_id") {
+            Some("epic")
+        } else {
+            match category {
+                Some("daily") => Some("daily"),
+                Some("scratch") => Some("scratch"),
+                _ => None,
+            }
+        };
+        if let Some(note_type) = note_type {
+            out = set_frontmatter_field(&out, "type", note_type);
+        }
+    }
+
+    if !has_key("tags") {
+        out = set_frontmatter_field(&out, "tags", "[]");
+    }
+
+    set_frontmatter_field(&out, "schema_version", "1")
+}
+
+/// The schema version of a note's frontmatter. Notes written before versioning are 0.
+pub fn schema_version(contents: &str) -> u32 {
+    NoteMeta::parse(contents).schema_version.unwrap_or(0)
+}
+
+/// Brings the frontmatter of `contents` up to `SCHEMA_VERSION`. Returns `None` when the
+/// note is already current, has no frontmatter or its frontmatter isn't valid YAML.
+pub fn migrate_contents(contents: &str, category: Option<&str>) -> Option<String> {
+    let (yaml, _) = split_frontmatter(contents)?;
+    serde_yaml::from_str::<serde_yaml::Value>(yaml).ok()?;
+
+    let version = schema_version(contents) as usize;
+    if version >= STEPS.len() {
+        return None;
+    }
+
+    let migrated = STEPS[version..]
+        .iter()
+        .fold(contents.to_string(), |acc, step| step(&acc, category));
+    (migrated != contents).then_some(migrated)
+}
+
+/// The category folder a note lives in, looking through the archive folder.
+fn note_category<'a>(notes_dir: &Path, path: &'a Path) -> Option<&'a str> {
+    let mut components = path.strip_prefix(notes_dir).ok()?.components();
+    let first = components.next()?.as_os_str().to_str()?;
+    if first == ARCHIVE_DIR {
+        return components.next()?.as_os_str().to_str();
+    }
+    Some(first)
+}
+
+/// A note whose frontmatter needs rewriting to the current schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteMigration {
+    pub path: PathBuf,
+    pub from_version: u32,
+    pub before: String,
+    pub after: String,
+}
+
+impl NoteMigration {
+    pub fn apply(&self) -> anyhow::Result<()> {
+        fs::write(&self.path, &self.after)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Unified diff of the note before and after the migration, `name` labels the file.
+    pub fn diff(&self, name: &str) -> String {
+        unified_diff(&self.before, &self.after, name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Line edits turning `before` into `after`, keeping their longest common subsequence.
+fn line_edits<'a>(before: &[&'a str], after: &[&'a str]) -> Vec<(Edit, &'a str)> {
+    // only the changed middle needs the quadratic table, the body of a note is unchanged
+    let prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &before[prefix..before.len() - suffix];
+    let new = &after[prefix..after.len() - suffix];
+
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits: Vec<(Edit, &str)> = before[..prefix].iter().map(|l| (Edit::Keep, *l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push((Edit::Keep, old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push((Edit::Remove, old[i]));
+            i += 1;
+        } else {
+            edits.push((Edit::Add, new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(before[before.len() - suffix..].iter().map(|l| (Edit::Keep, *l)));
+    edits
+}
+
+/// A unified diff with `DIFF_CONTEXT` lines of context, empty when nothing changed.
+fn unified_diff(before: &str, after: &str, name: &str) -> String {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+    let edits = line_edits(&before, &after);
+
+    // edit ranges of the hunks, merged when their context overlaps
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (idx, _) in edits.iter().enumerate().filter(|(_, (e, _))| *e != Edit::Keep) {
+        let start = idx.saturating_sub(DIFF_CONTEXT);
+        let end = (idx + DIFF_CONTEXT + 1).min(edits.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let count =
+        |edits: &[(Edit, &str)], skip: Edit| edits.iter().filter(|(e, _)| *e != skip).count();
+    let mut out = format!("--- a/{}\n+++ b/{}\n", name, name);
+    for (start, end) in hunks {
+        let hunk = &edits[start..end];
+        let (old_len, new_len) = (count(hunk, Edit::Add), count(hunk, Edit::Remove));
+        // an empty side starts at the line before it, as in diff -u
+        let old_start = count(&edits[..start], Edit::Add) + usize::from(old_len > 0);
+        let new_start = count(&edits[..start], Edit::Remove) + usize::from(new_len > 0);
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_len, new_start, new_len
+        ));
+        for (edit, line) in hunk {
+            let marker = match edit {
+                Edit::Keep => ' ',
+                Edit::Remove => '-',
+                Edit::Add => '+',
+            };
+            out.push_str(&format!("{}{}\n", marker, line));
+        }
+    }
+    out
+}
+
+/// Every note under `notes_dir` whose frontmatter is behind the current schema.
+pub fn plan_migrations(notes_dir: &Path) -> Vec<NoteMigration> {
+    let mut migrations: Vec<NoteMigration> = collect_markdown_files(notes_dir)
+        .into_iter()
+        .filter_map(|path| {
+            let before = fs::read_to_string(&path).ok()?;
+            let after = migrate_contents(&before, note_category(notes_dir, &path))?;
+            Some(NoteMigration {
+                from_version: schema_version(&before),
+                path,
+                before,
+                after,
+            })
+        })
+        .collect();
+
+    migrations.sort_by(|a, b| a.path.cmp(&b.path));
+    migrations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_unversioned_notes_once() {
+        let iteration =
+            "---\niteration_id: it-4\niteration_name: Sprint 4\ncreated: 2026-01-05\n---\nbody\n";
+        let migrated = migrate_contents(iteration, Some("iterations")).unwrap();
+        assert_eq!(
+            migrated,
+            "---\niteration_id: it-4\niteration_name: Sprint 4\ncreated: 2026-01-05\n\
+             type: iteration\ntags: []\nschema_version: 1\n---\nbody\n"
+        );
+        assert_eq!(migrate_contents(&migrated, Some("iterations")), None);
+
+        let daily = "---\ncreated: 2026-01-05\ntags:\n  - standup\n---\n";
+        assert_eq!(
+            migrate_contents(daily, Some("daily")).as_deref(),
+            Some(
+                "---\ncreated: 2026-01-05\ntags:\n  - standup\ntype: daily\nschema_version: 1\n---\n"
+            )
+        );
+
+        assert_eq!(migrate_contents("no frontmatter\n", None), None);
+    }
+
+    #[test]
+    fn normalizes_legacy_iteration_and_epic_keys() {
+        let iteration = "---\niteration_id: it-4\n\
+             iteration_link: https://app.shortcut.com/o/iteration/4\niteration_name: Sprint 4\ncreated: 2025-11-03\n---\n";
+        assert_eq!(
+            migrate_contents(iteration, Some("iterations")).as_deref(),
+            Some(
+                "---\niteration_id: it-4\niteration_link: https://app.shortcut.com/o/iteration/4\n\
+                 iteration_name: Sprint 4\ncreated: 2025-11-03\ntype: iteration\ntags: []\n\
+                 schema_version: 1\n---\n"
+            )
+        );
+
+        let epic = "---\nepic_id: EP-17\nepic_link: https://app.shortcut.com/o/epic/17\n\
+             epic_name: Billing\ncreated: 2025-11-03\n---\nnotes\n";
+        let migrated = migrate_contents(epic, Some("epics")).unwrap();
+        assert_eq!(
+            migrated,
+            "---\nepic_id: ep-17\nepic_link: https://app.shortcut.com/o/epic/17\n\
+             epic_name: Billing\ncreated: 2025-11-03\ntype: epic\ntags: []\n\
+             schema_version: 1\n---\nnotes\n"
+        );
+        assert_eq!(NoteMeta::parse(&migrated).note_type.as_deref(), Some("epic"));
+
+        let story = "---\nstory_id: sc-12\niteration: https://app.shortcut.com/o/iteration/4\n\
+             epic: 17\ncreated: 2025-11-03\ntype: General\ntags: []\n---\n";
+        assert_eq!(
+            migrate_contents(story, Some("stories")).as_deref(),
+            Some(
+                "---\nstory_id: sc-12\niteration_link: https://app.shortcut.com/o/iteration/4\n\
+                 epic_id: ep-17\ncreated: 2025-11-03\ntype: General\ntags: []\n\
+                 schema_version: 1\n---\n"
+            )
+        );
+    }
+
+    #[test]
+    fn diff_is_an_ordered_unified_diff() {
+        let migration = NoteMigration {
+            path: PathBuf::from("epics/billing.md"),
+            from_version: 0,
+            before: "---\nepic_id: 17\nepic_name: Billing\n---\na\nb\nc\nd\ne\n".to_string(),
+            after: "---\nepic_id: ep-17\nepic_name: Billing\ntags: []\n---\na\nb\nc\nd\ne\n"
+                .to_string(),
+        };
+        assert_eq!(
+            migration.diff("epics/billing.md"),
+            "--- a/epics/billing.md\n+++ b/epics/billing.md\n@@ -1,6 +1,7 @@\n \
+             ---\n-epic_id: 17\n+epic_id: ep-17\n epic_name: Billing\n+tags: []\n ---\n a\n b\n"
+        );
+
+        let unchanged = NoteMigration { after: migration.before.clone(), ..migration };
+        assert_eq!(unchanged.diff("epics/billing.md"), "");
+    }
+}
@@ -6,7 +6,18 @@ use std::{
 
 use crate::note::frontmatter::Frontmatter;
 
+pub mod capture;
+pub mod daily;
 pub mod frontmatter;
+pub mod links;
+pub mod manage;
+pub mod migrate;
+pub mod report;
+pub mod search;
+pub mod standup;
+pub mod sync;
+pub mod tags;
+pub mod todo;
 
 pub struct Note {
     pub frontmatter: Frontmatter,
@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use slugify::slugify;
+
+use crate::{
+    api::{iteration::Iteration, story::Story},
+    note::{
+        Note,
+        frontmatter::{NoteMeta, SCHEMA_VERSION, yaml_string},
+    },
+};
+
+/// Whether the note at `path` is a generated report (`type: report`) rather than a note.
+pub fn is_report(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .is_ok_and(|contents| NoteMeta::parse(&contents).note_type.as_deref() == Some("report"))
+}
+
+/// Headings of a story note that are copied into reports, matched case-insensitively.
+pub const REPORT_SECTIONS: [&str; 3] = ["Summary", "Outcome", "Decisions"];
+
+/// Returns `(heading, body)` for every heading in `contents` named like one of `names`.
+/// A section runs until the next heading of the same or a higher level.
+pub fn extract_sections(contents: &str, names: &[&str]) -> Vec<(String, String)> {
+    let heading = |line: &str| {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        let title = line[level..].strip_prefix(' ')?;
+        (level > 0).then(|| (level, title.trim().to_string()))
+    };
+
+    let mut sections = Vec::new();
+    let mut current: Option<(usize, String, Vec<&str>)> = None;
+
+    for line in contents.lines() {
+        let parsed = heading(line);
+
+        if let Some((level, _, _)) = &current
+            && parsed.as_ref().is_some_and(|(l, _)| l <= level)
+        {
+            let (_, title, body) = current.take().unwrap();
+            sections.push((title, body.join("\n").trim().to_string()));
+        }
+
+        match (&mut current, parsed) {
+            (Some((_, _, body)), _) => body.push(line),
+            (None, Some((level, title)))
+                if names.iter().any(|n| n.eq_ignore_ascii_case(&title)) =>
+            {
+                current = Some((level, title, Vec::new()));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((_, title, body)) = current {
+        sections.push((title, body.join("\n").trim().to_string()));
+    }
+
+    sections.retain(|(_, body)| !body.is_empty());
+    sections
+}
+
+/// Where the report of `iteration` is written, next to its iteration note in `iterations/`.
+pub fn report_path(notes_dir: &Path, iteration: &Iteration) -> PathBuf {
+    notes_dir
+        .join("iterations")
+        .join(format!("{}-report.md", slugify!(&iteration.name)))
+}
+
+fn story_state(story: &Story) -> &'static str {
+    if story.completed {
+        "Done"
+    } else if story.started {
+        "In progress"
+    } else {
+        "Unstarted"
+    }
+}
+
+fn format_estimate(estimate: Option<i32>) -> String {
+    estimate
+        .map(|e| e.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Builds a markdown report of `iteration`: a table of its stories with their state and
+/// estimate, followed by the key sections of every story note.
+pub fn build_iteration_report(
+    notes_dir: &Path,
+    iteration: &Iteration,
+    stories: &[Story],
+    today: NaiveDate,
+) -> String {
+    let mut out = format!(
+        "---\nschema_version: {}\niteration_id: it-{}\niteration_name: {}\ntype: report\n\
+         created: {}\ntags: []\n---\n\n",
+        SCHEMA_VERSION,
+        iteration.id,
+        yaml_string(&iteration.name),
+        today
+    );
+
+    out.push_str(&format!("# {} report\n\n", iteration.name));
+    out.push_str(&format!(
+        "{} → {} · [Shortcut]({}) · [[iterations/{}|Iteration note]]\n\n",
+        iteration.start_date,
+        iteration.end_date,
+        iteration.app_url,
+        slugify!(&iteration.name)
+    ));
+
+    let done = stories.iter().filter(|s| s.completed);
+    let done_points: i32 = done.clone().filter_map(|s| s.estimate).sum();
+    let total_points: i32 = stories.iter().filter_map(|s| s.estimate).sum();
+    out.push_str(&format!(
+        "{} of {} stories done · {} of {} points done\n\n",
+        done.count(),
+        stories.len(),
+        done_points,
+        total_points,
+    ));
+
+    out.push_str("| Story | State | Estimate |\n|---|---|---|\n");
+    for story in stories {
+        out.push_str(&format!(
+            "| [sc-{} {}]({}) | {} | {} |\n",
+            story.id,
+            story.name.replace('|', "\\|"),
+            story.app_url,
+            story_state(story),
+            format_estimate(story.estimate)
+        ));
+    }
+
+    for story in stories {
+        let note = Note::new(
+            &notes_dir,
+            story.id,
+            story.name.clone(),
+            story.app_url.clone(),
+            None,
+        );
+
+        out.push_str(&format!("\n## sc-{} {}\n\n", story.id, story.name));
+        out.push_str(&format!(
+            "{} · {} points · [Ticket]({})",
+            story_state(story),
+            format_estimate(story.estimate),
+            story.app_url
+        ));
+
+        let Ok(contents) = std::fs::read_to_string(&note.path) else {
+            out.push_str(" · no note\n");
+            continue;
+        };
+        out.push_str(&format!(
+            " · [[stories/{}|Note]]\n",
+            note.frontmatter.slug_id
+        ));
+
+        for (heading, body) in extract_sections(&contents, &REPORT_SECTIONS) {
+            out.push_str(&format!("\n### {}\n\n{}\n", heading, body));
+        }
+    }
+
+    out
+}
+
+/// Builds the report of `iteration` and writes it to `iterations/`. Returns
+/// the path of the report.
+pub fn write_iteration_report(
+    notes_dir: &Path,
+    iteration: &Iteration,
+    stories: &[Story],
+) -> anyhow::Result<PathBuf> {
+    let path = report_path(notes_dir, iteration);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let report = build_iteration_report(notes_dir, iteration, stories, crate::time::today());
+    std::fs::write(&path, report).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_sections_stops_at_sibling_heading() {
+        let contents = "# Title\n\n## Summary\n\nDid the thing.\n\n### Detail\n\nmore\n\n## Log\n\nnoise\n\n## decisions\n\nUse X\n";
+
+        assert_eq!(
+            extract_sections(contents, &REPORT_SECTIONS),
+            vec![
+                (
+                    "Summary".to_string(),
+                    "Did the thing.\n\n### Detail\n\nmore".to_string()
+                ),
+                ("decisions".to_string(), "Use X".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_frontmatter_parses_with_punctuated_iteration_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut iteration = crate::dummy::iteration();
+        iteration.name = "Sprint 4: login #2".to_string();
+        let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+
+        let report = build_iteration_report(dir.path(), &iteration, &[], today);
+        let meta = NoteMeta::parse(&report);
+        assert_eq!(meta.iteration_name.as_deref(), Some("Sprint 4: login #2"));
+        assert_eq!(meta.note_type.as_deref(), Some("report"));
+        assert_eq!(
+            report_path(dir.path(), &iteration),
+            dir.path().join("iterations/sprint-4-login-2-report.md")
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dbg_file,
+    note::{frontmatter::NoteMeta, todo::collect_markdown_files},
+};
+
+const INDEX_FILE: &str = "search_index.json";
+
+/// A single note as stored in the search index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedNote {
+    /// modification time in milliseconds since the epoch, used to skip unchanged notes
+    pub mtime: u64,
+    pub title: String,
+    pub tags: Vec<String>,
+    /// set for story notes, from the `story_id` frontmatter key
+    #[serde(default)]
+    pub story_id: Option<i32>,
+    /// every line of the file including the frontmatter, so hits map to file lines
+    pub lines: Vec<String>,
+}
+
+/// Full-text index over every note in the notes directory, persisted in `cache_dir`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    pub notes: HashMap<PathBuf, IndexedNote>,
+}
+
+// the index holds the contents of every note, keep it out of the update log
+impl fmt::Debug for SearchIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("notes", &self.notes.len())
+            .finish()
+    }
+}
+
+/// A parsed search query.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Query {
+    /// lowercased words and quoted phrases, all of which must appear in a note
+    pub terms: Vec<String>,
+    /// lowercased tags the note must have, from `tag:foo` or `#foo`
+    pub tags: Vec<String>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Query {
+        let mut query = Query::default();
+        let mut rest = input.trim();
+
+        while !rest.is_empty() {
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                let phrase = quoted[..end].trim();
+                if !phrase.is_empty() {
+                    query.terms.push(phrase.to_lowercase());
+                }
+                rest = quoted.get(end + 1..).unwrap_or("").trim_start();
+                continue;
+            }
+
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let word = &rest[..end];
+            rest = rest[end..].trim_start();
+
+            let tag = word.strip_prefix("tag:").or_else(|| word.strip_prefix('#'));
+            match tag {
+                Some(tag) if !tag.is_empty() => query.tags.push(tag.to_lowercase()),
+                _ => query.terms.push(word.to_lowercase()),
+            }
+        }
+
+        query
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// The best matching line of a note for a query.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub path: PathBuf,
+    pub title: String,
+    /// zero based line index within the file
+    pub line: usize,
+    pub snippet: String,
+    /// number of lines matching any term, used for ranking
+    pub score: usize,
+}
+
+fn file_mtime(path: &Path) -> Option<u64> {
+    let modified = path.metadata().ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+fn index_note(path: &Path, notes_dir: &Path, mtime: u64) -> Option<IndexedNote> {
+    let contents = read_to_string(path).ok()?;
+    let meta = NoteMeta::parse(&contents);
+    let title = meta.title().unwrap_or_else(|| {
+        path.strip_prefix(notes_dir)
+            .unwrap_or(path)
+            .with_extension("")
+            .display()
+            .to_string()
+    });
+
+    Some(IndexedNote {
+        mtime,
+        title,
+        tags: meta.tags.iter().map(|t| t.to_lowercase()).collect(),
+        story_id: meta.story_number(),
+        lines: contents.lines().map(|l| l.to_string()).collect(),
+    })
+}
+
+impl SearchIndex {
+    fn index_file(cache_dir: &Path) -> PathBuf {
+        cache_dir.join(INDEX_FILE)
+    }
+
+    /// Loads the persisted index, returning an empty one if it is missing or corrupt.
+    pub fn load(cache_dir: &Path) -> SearchIndex {
+        read_to_string(Self::index_file(cache_dir))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, cache_dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(cache_dir)?;
+        std::fs::write(Self::index_file(cache_dir), serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Re-reads notes whose mtime changed and drops notes that no longer exist.
+    /// Returns the number of notes that were (re)indexed.
+    pub fn refresh(&mut self, notes_dir: &Path) -> usize {
+        let files = collect_markdown_files(notes_dir);
+        let existing: HashSet<&PathBuf> = files.iter().collect();
+        self.notes.retain(|path, _| existing.contains(path));
+
+        let mut reindexed = 0;
+        for path in files {
+            let Some(mtime) = file_mtime(&path) else {
+                continue;
+            };
+
+            let is_fresh = self.notes.get(&path).is_some_and(|n| n.mtime == mtime);
+            if is_fresh {
+                continue;
+            }
+
+            if let Some(note) = index_note(&path, notes_dir, mtime) {
+                self.notes.insert(path, note);
+                reindexed += 1;
+            }
+        }
+
+        reindexed
+    }
+
+    /// Loads the index from `cache_dir`, brings it up to date and writes it back.
+    pub fn load_and_refresh(notes_dir: &Path, cache_dir: &Path) -> SearchIndex {
+        let mut index = Self::load(cache_dir);
+        let reindexed = index.refresh(notes_dir);
+
+        if reindexed > 0
+            && let Err(e) = index.save(cache_dir)
+        {
+            dbg_file!("Failed to save search index: {}", e);
+        }
+
+        index
+    }
+
+    /// Every tag used by any note, sorted and without duplicates.
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .notes
+            .values()
+            .flat_map(|note| note.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    pub fn search(&self, query: &Query) -> Vec<SearchHit> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits: Vec<SearchHit> = self
+            .notes
+            .iter()
+            .filter_map(|(path, note)| search_note(path, note, query))
+            .collect();
+
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        hits
+    }
+}
+
+fn search_note(path: &Path, note: &IndexedNote, query: &Query) -> Option<SearchHit> {
+    if !query.tags.iter().all(|tag| note.tags.contains(tag)) {
+        return None;
+    }
+
+    let lowered: Vec<String> = note.lines.iter().map(|l| l.to_lowercase()).collect();
+    let title = note.title.to_lowercase();
+
+    let all_terms_present = query
+        .terms
+        .iter()
+        .all(|term| title.contains(term) || lowered.iter().any(|l| l.contains(term)));
+    if !all_terms_present {
+        return None;
+    }
+
+    let matching_lines: Vec<usize> = lowered
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| query.terms.iter().any(|term| l.contains(term)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    // tag-only queries or title matches land on the first line
+    let line = matching_lines.first().copied().unwrap_or(0);
+
+    Some(SearchHit {
+        path: path.to_path_buf(),
+        title: note.title.clone(),
+        line,
+        snippet: note.lines.get(line).map(|l| l.trim().to_string()).unwrap_or_default(),
+        score: matching_lines.len(),
+    })
+}
+
+/// Byte ranges in `text` that case-insensitively match any of `terms`.
+pub fn highlight_ranges(text: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    let lowered = text.to_lowercase();
+    // lowercasing can change byte offsets for some scripts, skip highlighting then
+    if lowered.len() != text.len() {
+        return Vec::new();
+    }
+
+    let mut ranges: Vec<(usize, usize)> = terms
+        .iter()
+        .filter(|term| !term.is_empty())
+        .flat_map(|term| {
+            lowered
+                .match_indices(term.as_str())
+                .map(|(start, m)| (start, start + m.len()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    ranges.sort();
+
+    // merge overlapping ranges so spans can be built in order
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_parses_phrases_and_tags() {
+        let query = Query::parse(r#"Migration "data loss" tag:Backend #infra"#);
+
+        assert_eq!(query.terms, vec!["migration", "data loss"]);
+        assert_eq!(query.tags, vec!["backend", "infra"]);
+    }
+
+    #[test]
+    fn search_requires_all_terms_and_tags() {
+        let mut index = SearchIndex::default();
+        index.notes.insert(
+            PathBuf::from("a.md"),
+            IndexedNote {
+                mtime: 0,
+                title: "Note A".to_string(),
+                tags: vec!["backend".to_string()],
+                story_id: None,
+                lines: vec!["intro".to_string(), "the db migration plan".to_string()],
+            },
+        );
+        index.notes.insert(
+            PathBuf::from("b.md"),
+            IndexedNote {
+                mtime: 0,
+                title: "Note B".to_string(),
+                tags: vec![],
+                story_id: None,
+                lines: vec!["migration".to_string()],
+            },
+        );
+
+        let hits = index.search(&Query::parse("migration tag:backend"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, PathBuf::from("a.md"));
+        assert_eq!(hits[0].line, 1);
+
+        assert!(index.search(&Query::parse("\"plan migration\"")).is_empty());
+    }
+
+    #[test]
+    fn highlight_ranges_merges_overlaps() {
+        let terms = vec!["mig".to_string(), "migration".to_string()];
+        assert_eq!(highlight_ranges("The Migration", &terms), vec![(4, 13)]);
+    }
+}
@@ -0,0 +1,153 @@
+use std::{fs::read_to_string, path::Path};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{
+    api::story::Story,
+    note::daily::{DAILY_DIR, checked_items, previous_daily_note, unchecked_texts},
+};
+
+/// Yesterday/today/blockers lines for a standup update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Standup {
+    pub yesterday: Vec<String>,
+    pub today: Vec<String>,
+    pub blockers: Vec<String>,
+}
+
+/// The most recent weekday before `today`, so Monday's standup covers Friday.
+pub fn last_working_day(today: NaiveDate) -> NaiveDate {
+    let back = match today.weekday() {
+        Weekday::Mon => 3,
+        Weekday::Sun => 2,
+        _ => 1,
+    };
+    today - Duration::days(back)
+}
+
+fn story_label(story: &Story) -> String {
+    format!("sc-{} {}", story.id, story.name)
+}
+
+/// What happened to a story since `since`: a state change and/or new comments.
+fn story_activity(story: &Story, since: NaiveDate) -> Option<String> {
+    let moved = story
+        .moved_at
+        .is_some_and(|moved| moved.date_naive() >= since);
+    let comments = story
+        .comments
+        .iter()
+        .filter_map(|c| c.posted_at())
+        .filter(|posted| posted.date_naive() >= since)
+        .count();
+
+    let action = match (moved, story.completed, story.started) {
+        (true, true, _) => "Finished",
+        (true, false, true) => "Started",
+        (true, false, false) => "Moved",
+        (false, _, _) if comments > 0 => "Discussed",
+        _ => return None,
+    };
+
+    let mut line = format!("{} {}", action, story_label(story));
+    if comments > 0 {
+        let plural = if comments == 1 { "" } else { "s" };
+        line.push_str(&format!(" ({} new comment{})", comments, plural));
+    }
+    Some(line)
+}
+
+/// Builds a standup from story changes and comments since the last working day, the
+/// items ticked in that day's daily note and the stories that are blocked.
+pub fn build_standup(stories: &[Story], notes_dir: &Path, today: NaiveDate) -> Standup {
+    let since = last_working_day(today);
+    let daily_dir = notes_dir.join(DAILY_DIR);
+    let previous = previous_daily_note(&daily_dir, today)
+        .and_then(|path| read_to_string(path).ok())
+        .unwrap_or_default();
+
+    let mut yesterday: Vec<String> = stories
+        .iter()
+        .filter_map(|story| story_activity(story, since))
+        .collect();
+    yesterday.extend(checked_items(&previous));
+
+    let mut today_items: Vec<String> = stories
+        .iter()
+        .filter(|s| s.started && !s.completed && !s.blocked)
+        .map(|s| format!("Continue {}", story_label(s)))
+        .collect();
+
+    // open items from today's note, or the ones it will carry over when not created yet
+    let open = read_to_string(daily_dir.join(format!("{}.md", today))).unwrap_or(previous);
+    today_items.extend(unchecked_texts(&open));
+
+    let blockers = stories
+        .iter()
+        .filter(|s| s.blocked && !s.completed)
+        .map(story_label)
+        .collect();
+
+    Standup {
+        yesterday,
+        today: today_items,
+        blockers,
+    }
+}
+
+impl Standup {
+    /// Plain text that reads well when pasted into chat.
+    pub fn to_text(&self) -> String {
+        let section = |title: &str, items: &[String]| {
+            let mut out = format!("{}:\n", title);
+            if items.is_empty() {
+                out.push_str("- Nothing\n");
+            }
+            for item in items {
+                out.push_str(&format!("- {}\n", item));
+            }
+            out
+        };
+
+        [
+            section("Yesterday", &self.yesterday),
+            section("Today", &self.today),
+            section("Blockers", &self.blockers),
+        ]
+        .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_working_day_skips_weekend() {
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(
+            last_working_day(monday),
+            NaiveDate::from_ymd_opt(2026, 2, 27).unwrap()
+        );
+        assert_eq!(last_working_day(monday + Duration::days(1)), monday);
+    }
+
+    #[test]
+    fn standup_uses_previous_daily_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let daily = dir.path().join(DAILY_DIR);
+        std::fs::create_dir_all(&daily).unwrap();
+        std::fs::write(
+            daily.join("2026-02-27.md"),
+            "---\ntype: daily\n---\n- [x] Reviewed PR\n- [ ] Write docs\n",
+        )
+        .unwrap();
+
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let standup = build_standup(&[], dir.path(), monday);
+
+        assert_eq!(standup.yesterday, vec!["Reviewed PR"]);
+        assert_eq!(standup.today, vec!["Write docs"]);
+        assert!(standup.to_text().ends_with("Blockers:\n- Nothing\n"));
+    }
+}
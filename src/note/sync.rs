@@ -0,0 +1,149 @@
+use std::{path::Path, process::Output};
+
+use anyhow::Context;
+use tokio::{process::Command as TokioCommand, sync::Mutex};
+
+/// Held for every pull and commit, so a commit can't run in the middle of a pull's rebase
+/// and two commits can't race for the index.
+static SYNC_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Result of bringing the notes repository in line with its remote.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncStatus {
+    Synced,
+    /// Rebasing onto the remote conflicted in these files. The rebase has been aborted,
+    /// so the local notes are untouched.
+    Conflicts(Vec<String>),
+}
+
+async fn git(notes_dir: &Path, args: &[&str]) -> anyhow::Result<Output> {
+    TokioCommand::new("git")
+        .args(args)
+        .current_dir(notes_dir)
+        .output()
+        .await
+        .with_context(|| format!("failed to run git {}", args.join(" ")))
+}
+
+async fn git_checked(notes_dir: &Path, args: &[&str]) -> anyhow::Result<Output> {
+    let output = git(notes_dir, args).await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output)
+}
+
+pub async fn is_git_repo(notes_dir: &Path) -> bool {
+    git(notes_dir, &["rev-parse", "--is-inside-work-tree"])
+        .await
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+async fn has_upstream(notes_dir: &Path) -> bool {
+    git(notes_dir, &["rev-parse", "--abbrev-ref", "@{upstream}"])
+        .await
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Pulls and rebases onto the upstream branch. On conflicts the rebase is aborted and the
+/// conflicting files are returned.
+pub async fn pull(notes_dir: &Path) -> anyhow::Result<SyncStatus> {
+    let _guard = SYNC_LOCK.lock().await;
+    rebase_onto_upstream(notes_dir).await
+}
+
+async fn rebase_onto_upstream(notes_dir: &Path) -> anyhow::Result<SyncStatus> {
+    if !is_git_repo(notes_dir).await {
+        anyhow::bail!("{} is not a git repository", notes_dir.display());
+    }
+    if !has_upstream(notes_dir).await {
+        return Ok(SyncStatus::Synced);
+    }
+
+    let output = git(notes_dir, &["pull", "--rebase", "--autostash"]).await?;
+    if output.status.success() {
+        return Ok(SyncStatus::Synced);
+    }
+
+    let conflicted = git(notes_dir, &["diff", "--name-only", "--diff-filter=U"]).await?;
+    let files: Vec<String> = String::from_utf8_lossy(&conflicted.stdout)
+        .lines()
+        .map(|l| l.to_string())
+        .collect();
+
+    if files.is_empty() {
+        anyhow::bail!(
+            "git pull --rebase failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    git_checked(notes_dir, &["rebase", "--abort"]).await?;
+    Ok(SyncStatus::Conflicts(files))
+}
+
+/// Commits every change in the notes directory. Returns false when there was nothing to
+/// commit.
+pub async fn commit_all(notes_dir: &Path, message: &str) -> anyhow::Result<bool> {
+    git_checked(notes_dir, &["add", "-A"]).await?;
+
+    let staged = git(notes_dir, &["diff", "--cached", "--quiet"]).await?;
+    if staged.status.success() {
+        return Ok(false);
+    }
+
+    git_checked(notes_dir, &["commit", "-m", message]).await?;
+    Ok(true)
+}
+
+/// Commits the notes directory and pushes it. A rejected push is retried once after
+/// rebasing onto the remote.
+pub async fn commit_and_push(notes_dir: &Path, message: &str) -> anyhow::Result<SyncStatus> {
+    let _guard = SYNC_LOCK.lock().await;
+    if !commit_all(notes_dir, message).await? || !has_upstream(notes_dir).await {
+        return Ok(SyncStatus::Synced);
+    }
+
+    if git(notes_dir, &["push"]).await?.status.success() {
+        return Ok(SyncStatus::Synced);
+    }
+
+    match rebase_onto_upstream(notes_dir).await? {
+        SyncStatus::Synced => {
+            git_checked(notes_dir, &["push"]).await?;
+            Ok(SyncStatus::Synced)
+        }
+        conflicts => Ok(conflicts),
+    }
+}
+
+/// Commit message for an edit of `note`, referencing the story it belongs to.
+pub fn commit_message(story_id: Option<i32>, note: &str) -> String {
+    match story_id {
+        Some(id) => format!("sc-{}: update {}", id, note),
+        None => format!("Update {}", note),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_message_references_story() {
+        assert_eq!(
+            commit_message(Some(123), "Fix login"),
+            "sc-123: update Fix login"
+        );
+        assert_eq!(
+            commit_message(None, "daily/2026-10-18.md"),
+            "Update daily/2026-10-18.md"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{
+    api::story::Story,
+    note::{
+        frontmatter::{NoteMeta, set_frontmatter_field},
+        search::SearchIndex,
+    },
+};
+
+/// Normalizes user input into a tag: lowercase, no leading `#`, and anything other
+/// than letters, digits, `-`, `_` and `/` replaced with `-`.
+pub fn normalize_tag(input: &str) -> String {
+    input
+        .trim()
+        .trim_start_matches('#')
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '/') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Formats tags as a YAML flow sequence, quoting tags YAML would otherwise read as
+/// something other than a string (e.g. `2024` or `true`).
+fn tags_yaml(tags: &[String]) -> String {
+    let items: Vec<String> = tags
+        .iter()
+        .map(|tag| {
+            let plain = matches!(
+                serde_yaml::from_str(tag),
+                Ok(serde_yaml::Value::String(parsed)) if parsed == *tag
+            );
+            if plain {
+                tag.clone()
+            } else {
+                format!("\"{}\"", tag)
+            }
+        })
+        .collect();
+
+    format!("[{}]", items.join(", "))
+}
+
+/// Replaces the `tags` of a note's frontmatter.
+pub fn write_tags(contents: &str, tags: &[String]) -> String {
+    set_frontmatter_field(contents, "tags", &tags_yaml(tags))
+}
+
+/// Sets the tags of the note at `path`.
+pub fn set_note_tags(path: &Path, tags: &[String]) -> anyhow::Result<()> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    fs::write(path, write_tags(&contents, tags))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Adds any of `tags` the note at `path` doesn't have yet. Returns whether the note
+/// changed.
+pub fn merge_note_tags(path: &Path, tags: &[String]) -> anyhow::Result<bool> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut merged = NoteMeta::parse(&contents).tags;
+    let missing: Vec<&String> = tags.iter().filter(|t| !merged.contains(t)).collect();
+    if missing.is_empty() {
+        return Ok(false);
+    }
+
+    merged.extend(missing.into_iter().cloned());
+    fs::write(path, write_tags(&contents, &merged))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Story notes that are missing some of their story's labels as tags, with the tags to
+/// add to each.
+pub fn missing_story_labels(index: &SearchIndex, stories: &[Story]) -> Vec<(PathBuf, Vec<String>)> {
+    index
+        .notes
+        .iter()
+        .filter_map(|(path, note)| {
+            let story = stories.iter().find(|s| Some(s.id) == note.story_id)?;
+            let missing: Vec<String> = story
+                .labels
+                .iter()
+                .map(|label| normalize_tag(&label.name))
+                .filter(|tag| !tag.is_empty() && !note.tags.contains(tag))
+                .collect();
+            (!missing.is_empty()).then(|| (path.clone(), missing))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_tag_strips_hash_and_spaces() {
+        assert_eq!(normalize_tag(" #Front End "), "front-end");
+        assert_eq!(normalize_tag("infra/k8s"), "infra/k8s");
+    }
+
+    #[test]
+    fn write_tags_replaces_block_list() {
+        let contents = "---\nname: x\ntags:\n  - old\n  - older\ncreated: 2026-01-01\n---\nbody\n";
+        let tags = vec!["api".to_string(), "2024".to_string()];
+
+        assert_eq!(
+            write_tags(contents, &tags),
+            "---\nname: x\ntags: [api, \"2024\"]\ncreated: 2026-01-01\n---\nbody\n"
+        );
+    }
+}
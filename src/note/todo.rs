@@ -0,0 +1,262 @@
+use std::{
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::NaiveDate;
+
+use crate::note::frontmatter::{NoteMeta, frontmatter_line_count};
+
+const CHECKBOX_PREFIXES: &[&str] = &["- [", "* [", "+ ["];
+
+/// A single checkbox found in a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Todo {
+    /// zero based line index within the file
+    pub line: usize,
+    pub text: String,
+    pub checked: bool,
+    pub due: Option<NaiveDate>,
+}
+
+/// All checkboxes found in one note.
+#[derive(Debug, Clone)]
+pub struct TodoGroup {
+    pub path: PathBuf,
+    pub title: String,
+    pub story_id: Option<i32>,
+    pub todos: Vec<Todo>,
+}
+
+impl TodoGroup {
+    pub fn open_count(&self) -> usize {
+        self.todos.iter().filter(|t| !t.checked).count()
+    }
+}
+
+/// Parses a single line as a checkbox, returning `(checked, text)`.
+pub fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    let rest = CHECKBOX_PREFIXES
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))?;
+
+    let mut chars = rest.chars();
+    let mark = chars.next()?;
+    let rest = chars.as_str().strip_prefix(']')?;
+
+    let checked = match mark {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+
+    Some((checked, rest.trim()))
+}
+
+/// Extracts a due date written as `due:2026-02-18` or with the Obsidian Tasks
+/// `📅 2026-02-18` syntax.
+pub fn parse_due_date(text: &str) -> Option<NaiveDate> {
+    let mut words = text.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        let candidate = if let Some(date) = word.strip_prefix("due:") {
+            Some(date)
+        } else if word == "📅" {
+            words.peek().copied()
+        } else {
+            word.strip_prefix("📅")
+        };
+
+        if let Some(date) = candidate.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        {
+            return Some(date);
+        }
+    }
+
+    None
+}
+
+/// Parses every checkbox in `contents`, skipping the frontmatter and fenced code blocks.
+pub fn parse_todos(contents: &str) -> Vec<Todo> {
+    let skip = frontmatter_line_count(contents);
+    let mut in_code_block = false;
+    let mut todos = Vec::new();
+
+    for (idx, line) in contents.lines().enumerate().skip(skip) {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if let Some((checked, text)) = parse_checkbox(line) {
+            todos.push(Todo {
+                line: idx,
+                text: text.to_string(),
+                checked,
+                due: parse_due_date(text),
+            });
+        }
+    }
+
+    todos
+}
+
+/// Recursively collects every markdown file below `dir`, skipping hidden directories.
+pub fn collect_markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+
+            if is_hidden {
+                continue;
+            }
+
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+/// Scans every note under `notes_dir` and returns the notes that contain checkboxes.
+pub fn scan_todos(notes_dir: &Path) -> Vec<TodoGroup> {
+    collect_markdown_files(notes_dir)
+        .into_iter()
+        .filter_map(|path| {
+            let contents = read_to_string(&path).ok()?;
+            let todos = parse_todos(&contents);
+            if todos.is_empty() {
+                return None;
+            }
+
+            let meta = NoteMeta::parse(&contents);
+            let title = meta.title().unwrap_or_else(|| {
+                path.strip_prefix(notes_dir)
+                    .unwrap_or(&path)
+                    .with_extension("")
+                    .display()
+                    .to_string()
+            });
+
+            Some(TodoGroup {
+                title,
+                story_id: meta.story_number(),
+                path,
+                todos,
+            })
+        })
+        .collect()
+}
+
+/// Flips the checkbox on `line` of the note at `path`. `expected_text` guards against
+/// the file having changed since it was scanned.
+pub fn toggle_todo(path: &Path, line: usize, expected_text: &str) -> anyhow::Result<()> {
+    let contents =
+        read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let start: usize = contents.split_inclusive('\n').take(line).map(str::len).sum();
+    let target = contents[start..]
+        .lines()
+        .next()
+        .with_context(|| format!("Line {} no longer exists in {}", line + 1, path.display()))?;
+
+    let Some((checked, text)) = parse_checkbox(target) else {
+        anyhow::bail!("Line {} in {} is no longer a checkbox", line + 1, path.display());
+    };
+    if text != expected_text {
+        anyhow::bail!("{} changed since it was last scanned", path.display());
+    }
+
+    // the mark sits straight after the bullet and opening bracket, e.g. "- [", only that
+    // byte is replaced so line endings and the rest of the file are kept as they are
+    let mark_idx = start + target.len() - target.trim_start().len() + 3;
+    let new_mark = if checked { " " } else { "x" };
+    let mut new_contents = contents;
+    new_contents.replace_range(mark_idx..mark_idx + 1, new_mark);
+
+    write(path, new_contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_todos_reports_file_line_numbers() {
+        let contents = "---\ntype: daily\n---\n- [ ] first\ntext\n- [x] second\n";
+        let todos = parse_todos(contents);
+
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].line, 3);
+        assert_eq!(todos[0].text, "first");
+        assert!(!todos[0].checked);
+        assert_eq!(todos[1].line, 5);
+        assert!(todos[1].checked);
+    }
+
+    #[test]
+    fn parse_todos_ignores_code_blocks() {
+        let contents = "```\n- [ ] not a todo\n```\n- [ ] real\n";
+        let todos = parse_todos(contents);
+
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].text, "real");
+    }
+
+    #[test]
+    fn parse_due_date_supports_both_syntaxes() {
+        let expected = NaiveDate::from_ymd_opt(2026, 2, 18);
+        assert_eq!(parse_due_date("ship it due:2026-02-18"), expected);
+        assert_eq!(parse_due_date("ship it 📅 2026-02-18"), expected);
+        assert_eq!(parse_due_date("ship it"), None);
+    }
+
+    #[test]
+    fn toggle_todo_rewrites_only_target_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        std::fs::write(&path, "- [ ] one\n- [ ] two\n").unwrap();
+
+        toggle_todo(&path, 1, "two").unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), "- [ ] one\n- [x] two\n");
+
+        toggle_todo(&path, 1, "two").unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), "- [ ] one\n- [ ] two\n");
+
+        assert!(toggle_todo(&path, 0, "changed").is_err());
+    }
+
+    #[test]
+    fn toggle_todo_keeps_crlf_line_endings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        std::fs::write(&path, "# Todo\r\n- [ ] one\r\n- [ ] two").unwrap();
+
+        toggle_todo(&path, 2, "two").unwrap();
+        assert_eq!(
+            read_to_string(&path).unwrap(),
+            "# Todo\r\n- [ ] one\r\n- [x] two"
+        );
+        assert!(toggle_todo(&path, 3, "three").is_err());
+    }
+}
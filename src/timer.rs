@@ -0,0 +1,256 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{epic::EpicSlim, story::Story};
+
+const TIMER_FILE: &str = "timers.json";
+
+/// The story a timer ran for, kept with the session so reports don't need the API.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TimedStory {
+    pub id: i32,
+    pub name: String,
+    pub epic_id: Option<i32>,
+}
+
+impl From<&Story> for TimedStory {
+    fn from(story: &Story) -> Self {
+        Self {
+            id: story.id,
+            name: story.name.clone(),
+            epic_id: story.epic_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RunningTimer {
+    pub story: TimedStory,
+    pub start: DateTime<Utc>,
+}
+
+/// A finished stretch of work on a story.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TimeSession {
+    pub story: TimedStory,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Every timer session, stored as JSON in `cache_dir`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeLog {
+    pub running: Option<RunningTimer>,
+    pub sessions: Vec<TimeSession>,
+}
+
+impl TimeLog {
+    fn file(cache_dir: &Path) -> PathBuf {
+        cache_dir.join(TIMER_FILE)
+    }
+
+    /// Reads the log, starting an empty one if there is none yet.
+    pub fn load(cache_dir: &Path) -> anyhow::Result<TimeLog> {
+        let path = Self::file(cache_dir);
+        match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(_) => Ok(TimeLog::default()),
+        }
+    }
+
+    pub fn save(&self, cache_dir: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(cache_dir)?;
+        let path = Self::file(cache_dir);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Starts timing `story`, stopping the timer of any other story first. Returns the
+    /// session that was stopped.
+    pub fn start(&mut self, story: TimedStory, now: DateTime<Utc>) -> Option<TimeSession> {
+        if self
+            .running
+            .as_ref()
+            .is_some_and(|r| r.story.id == story.id)
+        {
+            return None;
+        }
+
+        let stopped = self.stop(now);
+        self.running = Some(RunningTimer { story, start: now });
+        stopped
+    }
+
+    /// Stops the running timer and records its session.
+    pub fn stop(&mut self, now: DateTime<Utc>) -> Option<TimeSession> {
+        let running = self.running.take()?;
+        let session = TimeSession {
+            story: running.story,
+            start: running.start,
+            end: now.max(running.start),
+        };
+        self.sessions.push(session.clone());
+        Some(session)
+    }
+
+    /// Keeps the timer on the active story: a running timer follows the active story
+    /// and stops when there is none. Does nothing when no timer is running.
+    pub fn follow_active_story(&mut self, active: Option<&Story>, now: DateTime<Utc>) -> bool {
+        if self.running.is_none() {
+            return false;
+        }
+
+        match active {
+            Some(story) => self.start(story.into(), now).is_some(),
+            None => self.stop(now).is_some(),
+        }
+    }
+
+    /// Sessions and the running timer as `(story, start, end)`, with the running timer
+    /// ending `now`.
+    fn spans(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Iterator<Item = (&TimedStory, DateTime<Utc>, DateTime<Utc>)> {
+        self.sessions
+            .iter()
+            .map(|s| (&s.story, s.start, s.end))
+            .chain(self.running.iter().map(move |r| (&r.story, r.start, now)))
+    }
+
+    /// Total time tracked on a story, including the running timer.
+    pub fn story_total(&self, story_id: i32, now: DateTime<Utc>) -> Duration {
+        self.spans(now)
+            .filter(|(story, _, _)| story.id == story_id)
+            .map(|(_, start, end)| end - start)
+            .sum()
+    }
+
+    /// Time tracked between `from` and `to` per story, clipping sessions that cross
+    /// either end.
+    pub fn totals_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<(TimedStory, Duration)> {
+        let mut totals: BTreeMap<i32, (TimedStory, Duration)> = BTreeMap::new();
+
+        for (story, start, end) in self.spans(now) {
+            let (start, end) = (start.max(from), end.min(to));
+            if end <= start {
+                continue;
+            }
+
+            totals
+                .entry(story.id)
+                .or_insert_with(|| (story.clone(), Duration::zero()))
+                .1 += end - start;
+        }
+
+        totals.into_values().collect()
+    }
+
+    /// A report of the week containing `date`, per story and per epic.
+    pub fn weekly_report(&self, date: NaiveDate, epics: &[EpicSlim], now: DateTime<Utc>) -> String {
+        let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let local_midnight = |day: NaiveDate| {
+            day.and_hms_opt(0, 0, 0)
+                .and_then(|t| t.and_local_timezone(Local).earliest())
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(|| day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        };
+        let totals = self.totals_between(
+            local_midnight(monday),
+            local_midnight(monday + Duration::days(7)),
+            now,
+        );
+
+        let mut out = format!("Week of {}\n", monday);
+        if totals.is_empty() {
+            out.push_str("\nNo time tracked\n");
+            return out;
+        }
+
+        out.push_str("\nBy story\n");
+        for (story, total) in &totals {
+            out.push_str(&format!(
+                "  {:>8}  sc-{} {}\n",
+                format_duration(*total),
+                story.id,
+                story.name
+            ));
+        }
+
+        let mut by_epic: BTreeMap<Option<i32>, Duration> = BTreeMap::new();
+        for (story, total) in &totals {
+            *by_epic.entry(story.epic_id).or_insert_with(Duration::zero) += *total;
+        }
+
+        out.push_str("\nBy epic\n");
+        for (epic_id, total) in &by_epic {
+            let name = match epic_id {
+                Some(id) => epics
+                    .iter()
+                    .find(|e| e.id == *id)
+                    .map(|e| e.name.clone())
+                    .unwrap_or_else(|| format!("Epic {}", id)),
+                None => "No epic".to_string(),
+            };
+            out.push_str(&format!("  {:>8}  {}\n", format_duration(*total), name));
+        }
+
+        let total: Duration = totals.iter().map(|(_, d)| *d).sum();
+        out.push_str(&format!("\nTotal {}\n", format_duration(total)));
+        out
+    }
+}
+
+/// Formats a duration like `2h 05m`, or `12m` when under an hour.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    match minutes / 60 {
+        0 => format!("{}m", minutes),
+        hours => format!("{}h {:02}m", hours, minutes % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn story(id: i32) -> TimedStory {
+        TimedStory {
+            id,
+            name: format!("Story {}", id),
+            epic_id: None,
+        }
+    }
+
+    #[test]
+    fn starting_another_story_stops_the_running_one() {
+        let at = |h| Utc.with_ymd_and_hms(2026, 3, 2, h, 0, 0).unwrap();
+        let mut log = TimeLog::default();
+
+        log.start(story(1), at(9));
+        let stopped = log.start(story(2), at(11)).unwrap();
+
+        assert_eq!(stopped.story.id, 1);
+        assert_eq!(log.story_total(1, at(12)), Duration::hours(2));
+        assert_eq!(log.story_total(2, at(12)), Duration::hours(1));
+
+        let totals = log.totals_between(at(10), at(23), at(12));
+        assert_eq!(totals[0].1, Duration::hours(1));
+        assert_eq!(format_duration(Duration::minutes(125)), "2h 05m");
+    }
+}
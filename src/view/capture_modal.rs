@@ -0,0 +1,65 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget, WidgetRef, Wrap},
+};
+
+use crate::{
+    api::story::Story, app::model::CaptureModalState, view::description_modal::centered_rect,
+};
+
+/// Single line input for jotting a thought into the active story's note, or today's
+/// daily note when no story is active.
+pub struct CaptureModal<'a> {
+    state: &'a CaptureModalState,
+    active_story: Option<&'a Story>,
+}
+
+impl<'a> CaptureModal<'a> {
+    pub fn new(state: &'a CaptureModalState, active_story: Option<&'a Story>) -> Self {
+        Self {
+            state,
+            active_story,
+        }
+    }
+}
+
+impl WidgetRef for CaptureModal<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(50, 30, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(" Quick Capture ");
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // spacer
+                Constraint::Length(1), // label
+                Constraint::Min(1),    // input
+                Constraint::Length(1), // hint
+            ])
+            .split(inner);
+
+        let target = match self.active_story {
+            Some(story) => format!("Add to sc-{} {}:", story.id, story.name),
+            None => "Add to today's daily note:".to_string(),
+        };
+        let label = Line::from(target).style(Style::default().add_modifier(Modifier::BOLD));
+        buf.set_line(chunks[1].x, chunks[1].y, &label, chunks[1].width);
+
+        Paragraph::new(format!("{}_", self.state.input))
+            .wrap(Wrap { trim: false })
+            .render(chunks[2], buf);
+
+        let hint =
+            Paragraph::new("Enter to capture  Esc to cancel").style(Style::default().dark_gray());
+        hint.render(chunks[3], buf);
+    }
+}
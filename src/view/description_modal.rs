@@ -2,20 +2,32 @@ use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect, Size},
     style::{Modifier, Style},
-    text::{Line, Text},
+    text::Line,
     widgets::{Block, BorderType, Paragraph, StatefulWidget, Widget, Wrap},
 };
 use tui_scrollview::{ScrollView, ScrollViewState};
 
-use crate::api::story::Story;
+use crate::{
+    api::{branch::PullRequest, story::Story},
+    view::markdown::render_markdown,
+    worktree::StoryRepo,
+};
 
 pub struct DescriptionModal<'a> {
     story: &'a Story,
+    /// Titles of notes that mention the story
+    mentioned_in: Vec<String>,
+    /// Repo remembered for the story
+    repo: Option<StoryRepo>,
 }
 
 impl<'a> DescriptionModal<'a> {
-    pub fn new(story: &'a Story) -> Self {
-        Self { story }
+    pub fn new(story: &'a Story, mentioned_in: Vec<String>, repo: Option<StoryRepo>) -> Self {
+        Self {
+            story,
+            mentioned_in,
+            repo,
+        }
     }
 }
 
@@ -55,13 +67,37 @@ impl StatefulWidget for DescriptionModal<'_> {
         // Description content with word wrap via ScrollView
         let content_area = chunks[2];
         let trimmed = self.story.description.trim();
-        let description = if trimmed.is_empty() {
+        let mut description = if trimmed.is_empty() {
             "No description".to_string()
         } else {
             trimmed.to_string()
         };
 
-        let paragraph = Paragraph::new(Text::from(description)).wrap(Wrap { trim: false });
+        if !self.mentioned_in.is_empty() {
+            description.push_str("\n\n---\n\n## Mentioned in notes\n\n");
+            for title in &self.mentioned_in {
+                description.push_str(&format!("- {}\n", title));
+            }
+        }
+
+        if let Some(branches) = branches_markdown(self.story) {
+            description.push_str("\n\n---\n\n");
+            description.push_str(&branches);
+        }
+
+        if let Some(repo) = &self.repo {
+            let worktree = if repo.worktree_path.is_dir() {
+                format!("`{}`", repo.worktree_path.display())
+            } else {
+                "not created".to_string()
+            };
+            description.push_str(&format!(
+                "\n\n---\n\n## Repository\n\n- Repo: `{}`\n- Worktree: {}\n",
+                repo.repo, worktree
+            ));
+        }
+
+        let paragraph = Paragraph::new(render_markdown(&description)).wrap(Wrap { trim: false });
 
         let content_width = content_area.width;
         let total_lines = paragraph.line_count(content_width) as u16;
@@ -75,6 +111,49 @@ impl StatefulWidget for DescriptionModal<'_> {
     }
 }
 
+fn pull_request_item(pr: &PullRequest) -> String {
+    let mut state = pr.status().label().to_string();
+    if let Some(review) = pr.review_label() {
+        state.push_str(&format!(", {}", review));
+    }
+    format!("[#{} {}]({}) ({})", pr.number, pr.title, pr.url, state)
+}
+
+/// Linked branches with their pull requests, then pull requests without a branch.
+fn branches_markdown(story: &Story) -> Option<String> {
+    let prs = story.linked_pull_requests();
+    if story.branches.is_empty() && prs.is_empty() {
+        return None;
+    }
+
+    let mut markdown = String::from("## Branches & pull requests\n\n");
+    for branch in &story.branches {
+        let state = if branch.merged() {
+            " (merged)"
+        } else if branch.deleted {
+            " (deleted)"
+        } else {
+            ""
+        };
+        markdown.push_str(&format!("- `{}`{}\n", branch.name, state));
+        for pr in &branch.pull_requests {
+            markdown.push_str(&format!("  - {}\n", pull_request_item(pr)));
+        }
+    }
+
+    let on_branch = |pr: &PullRequest| {
+        story
+            .branches
+            .iter()
+            .any(|b| b.pull_requests.iter().any(|p| p.id == pr.id))
+    };
+    for pr in prs.into_iter().filter(|pr| !on_branch(pr)) {
+        markdown.push_str(&format!("- {}\n", pull_request_item(pr)));
+    }
+
+    Some(markdown)
+}
+
 /// Calculate a centered rectangle with percentage-based sizing
 pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let width = (area.width * percent_x) / 100;
@@ -13,6 +13,9 @@ const LINES: &[&str] = &[
     "  Tab / L        Next view",
     "  BackTab / H    Prev view",
     "  d              Open daily note",
+    "  c              Quick capture",
+    "  S              Standup summary",
+    "  T              Start/stop timer",
     "─────────────────────────────────────",
     " Story List",
     "  Space          Show description",
@@ -25,8 +28,48 @@ const LINES: &[&str] = &[
     "  a              Select active story",
     "  f              Toggle finished",
     "─────────────────────────────────────",
+    " Iterations",
+    "  Enter          Open iteration note",
+    "  r              Generate report",
+    "  /              Search iterations",
+    "─────────────────────────────────────",
     " Notes",
     "  Enter          Open note",
+    "  Space          Collapse/expand section",
+    "  s              Cycle sort order",
+    "  n              New scratch note",
+    "  ^d / ^u        Scroll preview",
+    "  ] / [          Select link",
+    "  o              Follow link",
+    "  r              Rename note",
+    "  m              Move to category",
+    "  D              Delete note",
+    "  u              Undo delete",
+    "  a              Archive note",
+    "  A              Archive finished notes",
+    "  t              Edit note tags",
+    "  #              Filter notes by tag",
+    "─────────────────────────────────────",
+    " Search",
+    "  /              Search note contents",
+    "  Enter          Open note at match",
+    "─────────────────────────────────────",
+    " Todos",
+    "  Enter          Open note at line",
+    "  Space / x      Toggle checkbox",
+    "  f              Toggle done",
+    "─────────────────────────────────────",
+    " Worktrees",
+    "  r              Refresh",
+    "  D              Remove worktree",
+    "  X              Remove finished worktrees",
+    "  b              Also delete branches",
+    "─────────────────────────────────────",
+    " Sessions",
+    "  Enter          Switch to session",
+    "  r              Refresh",
+    "  D              Kill session",
+    "  X              Kill finished sessions",
     "─────────────────────────────────────",
     "       ? / Esc / q  close",
 ];
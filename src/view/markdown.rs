@@ -0,0 +1,303 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+use crate::note::frontmatter::split_frontmatter;
+
+/// Renders markdown into styled text for read-only previews. This is a line based
+/// renderer that covers what shows up in notes and story descriptions: frontmatter,
+/// headings, lists, checkboxes, quotes, rules, code blocks and inline emphasis/links.
+pub fn render_markdown(markdown: &str) -> Text<'static> {
+    let mut lines = Vec::new();
+
+    let body = match split_frontmatter(markdown) {
+        Some((yaml, body)) => {
+            let table = frontmatter_table(yaml);
+            if !table.is_empty() {
+                lines.extend(table);
+                lines.push(Line::from("─".repeat(40)).style(Style::default().dark_gray()));
+            }
+            body
+        }
+        None => markdown,
+    };
+
+    let mut in_code_block = false;
+    for raw in body.lines() {
+        let trimmed = raw.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            let lang = trimmed.trim_start_matches(['`', '~']).trim();
+            if in_code_block && !lang.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!(" {} ", lang),
+                    Style::default().dark_gray().italic(),
+                )));
+            }
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", raw),
+                Style::default().fg(Color::Cyan),
+            )));
+            continue;
+        }
+
+        lines.push(render_block_line(raw));
+    }
+
+    Text::from(lines)
+}
+
+/// Renders YAML frontmatter as a compact two column `key │ value` table.
+fn frontmatter_table(yaml: &str) -> Vec<Line<'static>> {
+    let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str(yaml) else {
+        return Vec::new();
+    };
+
+    let rows: Vec<(String, String)> = mapping
+        .iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?.to_string();
+            let value = yaml_value_to_string(value);
+            (!value.is_empty()).then_some((key, value))
+        })
+        .collect();
+
+    let key_width = rows.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+
+    rows.into_iter()
+        .map(|(key, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}", key, width = key_width),
+                    Style::default().dark_gray(),
+                ),
+                Span::styled(" │ ", Style::default().dark_gray()),
+                Span::raw(value),
+            ])
+        })
+        .collect()
+}
+
+fn yaml_value_to_string(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::Null => String::new(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Sequence(seq) => seq
+            .iter()
+            .map(yaml_value_to_string)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        serde_yaml::Value::Mapping(_) | serde_yaml::Value::Tagged(_) => "…".to_string(),
+    }
+}
+
+fn heading_style(level: usize) -> Style {
+    let color = match level {
+        1 => Color::Magenta,
+        2 => Color::Blue,
+        3 => Color::Cyan,
+        _ => Color::Green,
+    };
+    Style::default().fg(color).add_modifier(Modifier::BOLD)
+}
+
+/// Renders a single line outside of a code block.
+fn render_block_line(raw: &str) -> Line<'static> {
+    let trimmed = raw.trim_start();
+    let indent = " ".repeat(raw.len() - trimmed.len());
+
+    // Headings
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+        let text = trimmed[level..].trim();
+        let prefix = if level <= 2 { "" } else { "  " };
+        return Line::from(Span::styled(format!("{}{}", prefix, text), heading_style(level)));
+    }
+
+    // Horizontal rules
+    let compact: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.len() >= 3
+        && (compact.chars().all(|c| c == '-')
+            || compact.chars().all(|c| c == '*')
+            || compact.chars().all(|c| c == '_'))
+    {
+        return Line::from("─".repeat(40)).style(Style::default().dark_gray());
+    }
+
+    // Block quotes
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let mut spans = vec![Span::styled(
+            format!("{}│ ", indent),
+            Style::default().dark_gray(),
+        )];
+        spans.extend(
+            render_inline(quote.trim_start())
+                .into_iter()
+                .map(|s| s.patch_style(Style::default().italic())),
+        );
+        return Line::from(spans);
+    }
+
+    // Bullet lists and checkboxes
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = trimmed.strip_prefix(bullet) {
+            let (marker, done, text) = if let Some(text) = item.strip_prefix("[ ] ") {
+                ("☐ ", false, text)
+            } else if let Some(text) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                ("☑ ", true, text)
+            } else {
+                ("• ", false, item)
+            };
+
+            let marker_style = if done {
+                Style::default().green()
+            } else {
+                Style::default().yellow()
+            };
+            let mut spans = vec![Span::raw(indent), Span::styled(marker, marker_style)];
+            let inline = render_inline(text);
+            if done {
+                spans.extend(
+                    inline
+                        .into_iter()
+                        .map(|s| s.patch_style(Style::default().dark_gray().crossed_out())),
+                );
+            } else {
+                spans.extend(inline);
+            }
+            return Line::from(spans);
+        }
+    }
+
+    // Ordered lists
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && trimmed[digits..].starts_with(". ") {
+        let mut spans = vec![
+            Span::raw(indent),
+            Span::styled(
+                format!("{} ", &trimmed[..digits + 1]),
+                Style::default().yellow(),
+            ),
+        ];
+        spans.extend(render_inline(&trimmed[digits + 2..]));
+        return Line::from(spans);
+    }
+
+    let mut spans = vec![Span::raw(indent)];
+    spans.extend(render_inline(trimmed));
+    Line::from(spans)
+}
+
+/// Renders inline markdown: `code`, **bold**, *italic*, [links](url) and [[wikilinks]].
+pub fn render_inline(text: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let link_style = Style::default()
+        .fg(Color::Blue)
+        .add_modifier(Modifier::UNDERLINED);
+
+    while let Some(c) = rest.chars().next() {
+        let styled = if let Some(after) = rest.strip_prefix("[[") {
+            after.find("]]").map(|end| {
+                let target = &after[..end];
+                // show the alias of [[target|alias]] when there is one
+                let label = target.rsplit('|').next().unwrap_or(target);
+                (Span::styled(label.to_string(), link_style), &after[end + 2..])
+            })
+        } else if let Some(after) = rest.strip_prefix('[') {
+            after.find("](").and_then(|label_end| {
+                let url_part = &after[label_end + 2..];
+                url_part.find(')').map(|url_end| {
+                    let label = &after[..label_end];
+                    (Span::styled(label.to_string(), link_style), &url_part[url_end + 1..])
+                })
+            })
+        } else if let Some(after) = rest.strip_prefix('`') {
+            after.find('`').map(|end| {
+                (
+                    Span::styled(after[..end].to_string(), Style::default().fg(Color::Cyan)),
+                    &after[end + 1..],
+                )
+            })
+        } else if let Some(after) = rest.strip_prefix("**") {
+            after.find("**").filter(|end| *end > 0).map(|end| {
+                (
+                    Span::styled(after[..end].to_string(), Style::default().bold()),
+                    &after[end + 2..],
+                )
+            })
+        } else if let Some(after) = rest.strip_prefix('*') {
+            // underscores are left alone so snake_case identifiers survive
+            after
+                .find('*')
+                .filter(|end| *end > 0 && !after.starts_with(' '))
+                .map(|end| {
+                    (
+                        Span::styled(after[..end].to_string(), Style::default().italic()),
+                        &after[end + 1..],
+                    )
+                })
+        } else {
+            None
+        };
+
+        match styled {
+            Some((span, remaining)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.push(span);
+                rest = remaining;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn frontmatter_is_rendered_as_table() {
+        let text = render_markdown("---\nstory_id: sc-1\ntags: []\n---\n# Title\n");
+        let lines: Vec<String> = text.lines.iter().map(line_text).collect();
+
+        assert_eq!(lines[0], "story_id │ sc-1");
+        assert!(lines[1].starts_with('─'));
+        assert_eq!(lines[2], "Title");
+    }
+
+    #[test]
+    fn inline_links_and_checkboxes() {
+        let text = render_markdown("- [x] see [[stories/foo|Foo]] and [docs](https://x.y)");
+        assert_eq!(line_text(&text.lines[0]), "☑ see Foo and docs");
+    }
+}
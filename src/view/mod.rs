@@ -1,12 +1,23 @@
 pub mod action_item_builder;
+pub mod capture_modal;
 pub mod create_note_modal;
 pub mod epic_list;
 pub mod keybinds_panel;
 pub mod description_modal;
+pub mod markdown;
 pub mod navbar;
+pub mod note_action_modal;
+pub mod note_links;
+pub mod note_preview;
+pub mod note_search;
 pub mod notes_list;
+pub mod repo_picker;
+pub mod session_list;
+pub mod standup_modal;
 pub mod story_item_builder;
 pub mod story_list;
+pub mod todo_list;
+pub mod worktree_list;
 pub mod iteration_list;
 
 pub use action_item_builder::ActionItemWidget;
@@ -15,6 +15,10 @@ pub struct NavBar {
     loading: LoadingState,
     has_stories: bool,
     tick: usize,
+    /// Label of the running timer, e.g. `sc-101 1h 05m`
+    timer: Option<String>,
+    /// The last notes sync conflicted and needs resolving with git
+    sync_conflict: bool,
 }
 
 impl NavBar {
@@ -23,12 +27,16 @@ impl NavBar {
         loading: LoadingState,
         has_stories: bool,
         tick: usize,
+        timer: Option<String>,
+        sync_conflict: bool,
     ) -> Self {
         Self {
             active_view,
             loading,
             has_stories,
             tick,
+            timer,
+            sync_conflict,
         }
     }
 
@@ -70,22 +78,42 @@ impl WidgetRef for NavBar {
         let paragraph = Paragraph::new(line);
         paragraph.render(inner, buf);
 
+        let mut right = Vec::new();
+        if self.sync_conflict {
+            right.push(Span::styled(
+                "⚠ notes sync conflict",
+                Style::default().fg(Color::Red),
+            ));
+        }
+        if let Some(timer) = &self.timer {
+            if !right.is_empty() {
+                right.push(Span::raw("  "));
+            }
+            right.push(Span::styled(
+                format!("⏱ {}", timer),
+                Style::default().fg(Color::Green),
+            ));
+        }
+
         // Show spinner on right when loading AND we have cached stories displayed
         if self.loading.is_loading() && self.has_stories {
+            if !right.is_empty() {
+                right.push(Span::raw("  "));
+            }
             let loading_text = format!("{} {}", self.spinner_char(), self.loading.label());
-            let loading_span = Span::styled(loading_text.clone(), Style::default().gray());
-            let loading_width = loading_text.len() as u16;
+            right.push(Span::styled(loading_text, Style::default().gray()));
+        }
 
-            if inner.width > loading_width {
-                let loading_area = Rect::new(
-                    inner.x + inner.width - loading_width,
-                    inner.y,
-                    loading_width,
-                    1,
-                );
-                let loading_paragraph = Paragraph::new(Line::from(loading_span));
-                loading_paragraph.render(loading_area, buf);
-            }
+        let right = Line::from(right);
+        let right_width = right.width() as u16;
+        if right_width > 0 && inner.width > right_width {
+            let right_area = Rect::new(
+                inner.x + inner.width - right_width,
+                inner.y,
+                right_width,
+                1,
+            );
+            Paragraph::new(right).render(right_area, buf);
         }
     }
 }
@@ -0,0 +1,184 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget, WidgetRef, Wrap},
+};
+
+use crate::{
+    app::{
+        model::{NoteAction, NoteActionModalState},
+        pane::note_action_modal::suggestions,
+    },
+    note::manage::CATEGORIES,
+    view::description_modal::centered_rect,
+};
+
+pub struct NoteActionModal<'a> {
+    state: &'a NoteActionModalState,
+}
+
+impl<'a> NoteActionModal<'a> {
+    pub fn new(state: &'a NoteActionModalState) -> Self {
+        Self { state }
+    }
+
+    fn note_name(&self) -> String {
+        self.state
+            .paths
+            .first()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Selected tags, the input line and completions for the tag editor.
+    fn tag_lines(&self) -> Vec<Line<'static>> {
+        let selected = if self.state.tags.is_empty() {
+            Line::from("no tags").style(Style::default().dark_gray())
+        } else {
+            Line::from(
+                self.state
+                    .tags
+                    .iter()
+                    .map(|tag| format!("#{}", tag))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+            .style(Style::default().cyan())
+        };
+
+        let mut lines = vec![
+            selected,
+            Line::from(""),
+            Line::from(format!("> {}_", self.state.input)),
+        ];
+        lines.extend(
+            suggestions(self.state)
+                .into_iter()
+                .map(|tag| Line::from(format!("  #{}", tag)).style(Style::default().dark_gray())),
+        );
+        lines
+    }
+}
+
+impl WidgetRef for NoteActionModal<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let Some(action) = self.state.action else {
+            return;
+        };
+
+        let popup_area = centered_rect(50, 40, area);
+
+        let title = match action {
+            NoteAction::Rename => " Rename Note ",
+            NoteAction::Delete => " Delete Note ",
+            NoteAction::Move => " Move Note ",
+            NoteAction::Archive | NoteAction::ArchiveFinished => " Archive Notes ",
+            NoteAction::EditTags => " Note Tags ",
+            NoteAction::FilterTags => " Filter Notes by Tag ",
+        };
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(title);
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // spacer
+                Constraint::Length(1), // label
+                Constraint::Min(1),    // body
+                Constraint::Length(1), // hint
+            ])
+            .split(inner);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let (label, body, hint): (String, Vec<Line>, &str) = match action {
+            NoteAction::Rename => (
+                format!("New name for {}:", self.note_name()),
+                vec![Line::from(format!("{}_", self.state.input))],
+                "Enter to rename  Esc to cancel",
+            ),
+
+            NoteAction::Delete => (
+                format!("Delete {}?", self.note_name()),
+                vec![
+                    Line::from("It can be restored with u for a few seconds.")
+                        .style(Style::default().dark_gray()),
+                ],
+                "y to delete  n to cancel",
+            ),
+
+            NoteAction::Move => (
+                format!("Move {} to:", self.note_name()),
+                CATEGORIES
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, category)| {
+                        if idx == self.state.category {
+                            Line::from(format!("▶ {}", category)).style(Style::default().yellow())
+                        } else {
+                            Line::from(format!("  {}", category))
+                        }
+                    })
+                    .collect(),
+                "j/k select  Enter to move  Esc to cancel",
+            ),
+
+            NoteAction::EditTags | NoteAction::FilterTags => {
+                let label = match action {
+                    NoteAction::EditTags => format!("Tags of {}:", self.note_name()),
+                    _ => "Only show notes tagged:".to_string(),
+                };
+                let hint = match action {
+                    NoteAction::EditTags => {
+                        "Enter add/remove  Tab complete  Enter on empty to save"
+                    }
+                    _ => "Enter add/remove  Tab complete  Enter on empty to apply",
+                };
+                (label, self.tag_lines(), hint)
+            }
+
+            NoteAction::Archive | NoteAction::ArchiveFinished => {
+                if self.state.paths.is_empty() {
+                    (
+                        "Nothing to archive.".to_string(),
+                        vec![
+                            Line::from("No notes belong to completed stories or ended iterations.")
+                                .style(Style::default().dark_gray()),
+                        ],
+                        "Esc to close",
+                    )
+                } else {
+                    let label = match self.state.paths.len() {
+                        1 => format!("Move {} to archive/?", self.note_name()),
+                        n => format!("Move {} finished notes to archive/?", n),
+                    };
+                    let body = self
+                        .state
+                        .paths
+                        .iter()
+                        .filter_map(|p| p.file_name())
+                        .map(|n| Line::from(format!("  {}", n.to_string_lossy())))
+                        .collect();
+                    (label, body, "y to archive  n to cancel")
+                }
+            }
+        };
+
+        let label = Line::from(label).style(bold);
+        buf.set_line(chunks[1].x, chunks[1].y, &label, chunks[1].width);
+
+        Paragraph::new(body)
+            .wrap(Wrap { trim: false })
+            .render(chunks[2], buf);
+
+        Paragraph::new(hint)
+            .style(Style::default().dark_gray())
+            .render(chunks[3], buf);
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget, WidgetRef},
+};
+
+use crate::note::links::LinkGraph;
+
+/// Outgoing links and backlinks of the selected note.
+pub struct NoteLinksView<'a> {
+    graph: &'a LinkGraph,
+    path: Option<&'a Path>,
+    selected: Option<usize>,
+}
+
+impl<'a> NoteLinksView<'a> {
+    pub fn new(graph: &'a LinkGraph, path: Option<&'a Path>, selected: Option<usize>) -> Self {
+        Self {
+            graph,
+            path,
+            selected,
+        }
+    }
+
+    /// Height needed to show every link, capped at `max`.
+    pub fn height(&self, max: u16) -> u16 {
+        let Some(path) = self.path else {
+            return 3;
+        };
+
+        let rows = self.graph.outgoing(path).len().max(1) + self.graph.backlinks(path).len().max(1);
+        // two section headers and the border
+        ((rows + 4) as u16).min(max)
+    }
+
+    fn row(&self, idx: usize, arrow: &'static str, label: String, resolved: bool) -> Line<'static> {
+        let is_selected = self.selected == Some(idx);
+        let marker = if is_selected { "▶ " } else { "  " };
+
+        let style = match (is_selected, resolved) {
+            (true, _) => Style::default().fg(Color::Yellow).bold(),
+            (false, true) => Style::default().fg(Color::Blue),
+            (false, false) => Style::default().dark_gray().italic(),
+        };
+
+        Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Yellow)),
+            Span::styled(arrow, Style::default().dark_gray()),
+            Span::styled(label, style),
+        ])
+    }
+}
+
+impl WidgetRef for NoteLinksView<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_set(border::THICK)
+            .title(" Links ")
+            .title_bottom(" ]/[ select • o follow ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(path) = self.path else {
+            return;
+        };
+
+        let header = Style::default().dark_gray().bold();
+        let empty = Style::default().dark_gray();
+        let mut lines = vec![Line::from(Span::styled("Links to", header))];
+
+        let outgoing = self.graph.outgoing(path);
+        if outgoing.is_empty() {
+            lines.push(Line::from(Span::styled("  none", empty)));
+        }
+        for (idx, link) in outgoing.iter().enumerate() {
+            let label = match &link.path {
+                Some(_) => link.label.clone(),
+                None => format!("{} (missing)", link.label),
+            };
+            lines.push(self.row(idx, "→ ", label, link.path.is_some()));
+        }
+
+        lines.push(Line::from(Span::styled("Linked from", header)));
+
+        let backlinks = self.graph.backlinks(path);
+        if backlinks.is_empty() {
+            lines.push(Line::from(Span::styled("  none", empty)));
+        }
+        for (offset, source) in backlinks.into_iter().enumerate() {
+            let idx = outgoing.len() + offset;
+            lines.push(self.row(idx, "← ", self.graph.title(source), true));
+        }
+
+        // keep the selected row in view
+        let selected_line = self
+            .selected
+            .map(|idx| {
+                if idx < outgoing.len() {
+                    idx + 1
+                } else {
+                    // skip the "none" placeholder and the backlinks header
+                    idx + outgoing.is_empty() as usize + 2
+                }
+            })
+            .unwrap_or(0) as u16;
+        let scroll = selected_line.saturating_sub(inner.height.saturating_sub(1));
+
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
+    }
+}
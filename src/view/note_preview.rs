@@ -0,0 +1,57 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect, Size},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Paragraph, StatefulWidget, Widget, Wrap},
+};
+use tui_scrollview::{ScrollView, ScrollViewState};
+
+use crate::view::markdown::render_markdown;
+
+/// Read-only rendered preview of a note, scrollable with a `ScrollViewState`.
+pub struct NotePreview<'a> {
+    title: &'a str,
+    contents: &'a str,
+}
+
+impl<'a> NotePreview<'a> {
+    pub fn new(title: &'a str, contents: &'a str) -> Self {
+        Self { title, contents }
+    }
+}
+
+impl StatefulWidget for NotePreview<'_> {
+    type State = ScrollViewState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::bordered()
+            .border_set(border::THICK)
+            .title(format!(" {} ", self.title))
+            .title_bottom(" ^d/^u scroll ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.contents.trim().is_empty() {
+            let paragraph = Paragraph::new("Nothing to preview.")
+                .style(Style::default().gray())
+                .alignment(Alignment::Center);
+
+            if inner.height > 0 {
+                let centered = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                paragraph.render(centered, buf);
+            }
+            return;
+        }
+
+        let paragraph = Paragraph::new(render_markdown(self.contents)).wrap(Wrap { trim: false });
+
+        // leave a column for the scrollbar
+        let content_width = inner.width.saturating_sub(1);
+        let total_lines = paragraph.line_count(content_width) as u16;
+
+        let mut scroll_view = ScrollView::new(Size::new(content_width, total_lines));
+        scroll_view.render_widget(paragraph, Rect::new(0, 0, content_width, total_lines));
+        scroll_view.render(inner, buf, state);
+    }
+}
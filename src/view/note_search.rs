@@ -0,0 +1,145 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, StatefulWidget, Widget, WidgetRef},
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    app::model::NoteSearchState,
+    note::search::{Query, SearchHit, highlight_ranges},
+};
+
+pub struct NoteSearchView<'a> {
+    state: &'a NoteSearchState,
+}
+
+impl<'a> NoteSearchView<'a> {
+    pub fn new(state: &'a NoteSearchState) -> Self {
+        Self { state }
+    }
+}
+
+/// Renders one search result as a title line and a highlighted snippet line.
+struct SearchHitWidget<'a> {
+    hit: &'a SearchHit,
+    terms: &'a [String],
+    is_selected: bool,
+}
+
+impl SearchHitWidget<'_> {
+    fn snippet_line(&self) -> Line<'static> {
+        let snippet = &self.hit.snippet;
+        let mut spans = vec![Span::styled(
+            format!("    {:>4}: ", self.hit.line + 1),
+            Style::default().dark_gray(),
+        )];
+
+        let mut pos = 0;
+        for (start, end) in highlight_ranges(snippet, self.terms) {
+            if start > pos {
+                spans.push(Span::raw(snippet[pos..start].to_string()));
+            }
+            spans.push(Span::styled(
+                snippet[start..end].to_string(),
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ));
+            pos = end;
+        }
+        if pos < snippet.len() {
+            spans.push(Span::raw(snippet[pos..].to_string()));
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Widget for SearchHitWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 {
+            return;
+        }
+
+        let title_style = if self.is_selected {
+            Style::default().fg(Color::Yellow).bold()
+        } else {
+            Style::default().bold()
+        };
+        let marker = if self.is_selected { "▶ " } else { "  " };
+        let title = Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Yellow)),
+            Span::styled(self.hit.title.clone(), title_style),
+        ]);
+        buf.set_line(area.x, area.y, &title, area.width);
+
+        if area.height >= 2 {
+            buf.set_line(area.x, area.y + 1, &self.snippet_line(), area.width);
+        }
+    }
+}
+
+impl WidgetRef for NoteSearchView<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(area);
+
+        let query = &self.state.search_query;
+        let display = if query.is_empty() && !self.state.search_active {
+            "/ to search notes  (\"phrase\", tag:name)".to_string()
+        } else {
+            format!("{}_", query)
+        };
+        let bar_style = if self.state.search_active {
+            Style::new().yellow()
+        } else {
+            Style::new().dark_gray()
+        };
+        Paragraph::new(display)
+            .block(Block::bordered().title(" Search "))
+            .style(bar_style)
+            .render(chunks[0], buf);
+
+        let title = format!(" {} results ", self.state.results.len());
+        let block = Block::bordered().border_set(border::THICK).title(title);
+        let inner = block.inner(chunks[1]);
+        block.render(chunks[1], buf);
+
+        if self.state.results.is_empty() {
+            let message = if self.state.indexing {
+                "Indexing notes..."
+            } else if query.is_empty() {
+                "Type a query to search note contents."
+            } else {
+                "No results."
+            };
+            let paragraph = Paragraph::new(message)
+                .style(Style::default().gray())
+                .alignment(Alignment::Center);
+
+            if inner.height > 0 {
+                let centered = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                paragraph.render(centered, buf);
+            }
+            return;
+        }
+
+        let terms = Query::parse(query).terms;
+        let results = &self.state.results;
+        let builder = ListBuilder::new(|context| {
+            let widget = SearchHitWidget {
+                hit: &results[context.index],
+                terms: &terms,
+                is_selected: context.is_selected,
+            };
+            (widget, 2)
+        });
+
+        let list = ListView::new(builder, results.len());
+        let mut list_state = ListState::default();
+        list_state.select(self.state.selected);
+
+        StatefulWidget::render(list, inner, buf, &mut list_state);
+    }
+}
@@ -1,27 +1,79 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
 
 use chrono::NaiveDate;
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Layout, Rect},
-    style::Style,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
     symbols::border,
-    text::Line,
-    widgets::{Block, Paragraph, Widget, WidgetRef},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, StatefulWidget, Widget, WidgetRef},
 };
+use tui_widget_list::{ListBuilder, ListState, ListView};
 
-use crate::app::model::NotesListState;
+use crate::{
+    api::story::Story,
+    app::{
+        model::{NoteInfo, NotesListState},
+        pane::notes_list::{
+            NoteRow, category_notes, matches_tag_filter, selected_row, visible_rows,
+        },
+    },
+};
 
 pub struct NotesListView<'a> {
     state: &'a NotesListState,
+    stories: &'a [Story],
+    now: SystemTime,
 }
 
 impl<'a> NotesListView<'a> {
-    pub fn new(state: &'a NotesListState) -> Self {
-        Self { state }
+    pub fn new(state: &'a NotesListState, stories: &'a [Story]) -> Self {
+        Self {
+            state,
+            stories,
+            now: SystemTime::now(),
+        }
+    }
+}
+
+fn category_label(category: &str) -> &'static str {
+    match category {
+        "daily" => "Daily Notes",
+        "stories" => "Story Notes",
+        "iterations" => "Iteration Notes",
+        "epics" => "Epic Notes",
+        _ => "Scratch Notes",
+    }
+}
+
+/// Formats how long ago a note was modified, e.g. "5m" or "3d".
+fn format_age(modified: SystemTime, now: SystemTime) -> String {
+    let secs = now
+        .duration_since(modified)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+
+    match secs {
+        0..60 => "now".to_string(),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        86400..604800 => format!("{}d", secs / 86400),
+        _ => format!("{}w", secs / 604800),
     }
 }
 
+/// Formats tags like `#backend #infra`.
+fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{}", tag))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Format a daily note stem like `2026-02-18` into "Tue, Feb 18 2026"
 fn format_daily_name(stem: &str) -> Option<String> {
     let date = NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok()?;
@@ -45,131 +97,182 @@ fn format_slug(stem: &str) -> String {
         .join(" ")
 }
 
-fn display_name(path: &Path, is_daily: bool) -> String {
+/// Title for a note: the frontmatter title when there is one, otherwise derived from
+/// the file name.
+fn display_name(path: &Path, info: Option<&NoteInfo>, is_daily: bool) -> String {
+    if let Some(title) = info.and_then(|i| i.title.as_ref()) {
+        return title.clone();
+    }
+
     let stem = match path.file_stem().and_then(|s| s.to_str()) {
         Some(s) => s,
         None => return "???".to_string(),
     };
 
-    if is_daily
-        && let Some(formatted) = format_daily_name(stem)
-    {
+    if is_daily && let Some(formatted) = format_daily_name(stem) {
         return formatted;
     }
 
     format_slug(stem)
 }
 
-impl<'a> WidgetRef for NotesListView<'a> {
-    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-        let s = self.state;
-        if s.daily_notes.is_empty()
-            && s.story_notes.is_empty()
-            && s.iteration_notes.is_empty()
-            && s.epic_notes.is_empty()
-            && s.scratch_notes.is_empty()
-        {
-            let block = Block::bordered().border_set(border::THICK);
-            let inner = block.inner(area);
-            block.render(area, buf);
-
-            let message = "No notes found.";
-            let style = Style::default().gray();
-            let paragraph = Paragraph::new(message)
-                .style(style)
-                .alignment(Alignment::Center);
+/// One rendered row of the notes tree.
+struct NoteRowWidget<'a> {
+    title: String,
+    /// `(text, style)` columns shown right aligned
+    columns: Vec<(String, Style)>,
+    style: Style,
+    prefix: &'a str,
+}
 
-            if inner.height > 0 {
-                let centered_area =
-                    Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
-                paragraph.render(centered_area, buf);
-            }
-            return;
+impl Widget for NoteRowWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut right: Vec<Span> = Vec::new();
+        for (text, style) in self.columns {
+            right.push(Span::raw(" "));
+            right.push(Span::styled(text, style));
         }
+        let right = Line::from(right);
+        let right_width = (right.width() as u16).min(area.width);
 
-        let sections: Vec<(&str, &[PathBuf], bool)> = vec![
-            ("Daily Notes",     &self.state.daily_notes,     true),
-            ("Story Notes",     &self.state.story_notes,     false),
-            ("Iteration Notes", &self.state.iteration_notes, false),
-            ("Epic Notes",      &self.state.epic_notes,      false),
-            ("Scratch Notes",   &self.state.scratch_notes,   false),
-        ];
-
-        let mut constraints = Vec::new();
-        for (_, notes, _) in &sections {
-            if notes.is_empty() {
-                continue;
-            }
-            constraints.push(Constraint::Length(1));
-            // notes * 2 lines + 1 for border (no trailing divider on last item)
-            constraints.push(Constraint::Length((notes.len() * 2 + 1) as u16));
-            constraints.push(Constraint::Length(1));
-        }
+        let left =
+            Line::from(vec![Span::raw(self.prefix), Span::raw(self.title)]).style(self.style);
+        let left_width = area.width.saturating_sub(right_width + 1);
+        buf.set_style(area, self.style);
+        buf.set_line(area.x, area.y, &left, left_width);
+        buf.set_line(
+            area.x + area.width - right_width,
+            area.y,
+            &right,
+            right_width,
+        );
+    }
+}
 
-        if !constraints.is_empty() {
-            constraints.pop();
-        }
-        constraints.push(Constraint::Min(0));
+impl NotesListView<'_> {
+    fn story_state(&self, story_id: i32) -> Option<(String, Style)> {
+        let story = self.stories.iter().find(|s| s.id == story_id)?;
+        Some(if story.completed {
+            ("done".to_string(), Style::default().fg(Color::Green))
+        } else if story.started {
+            (
+                "in progress".to_string(),
+                Style::default().fg(Color::Yellow),
+            )
+        } else {
+            ("unstarted".to_string(), Style::default().dark_gray())
+        })
+    }
 
-        let section_areas = Layout::vertical(constraints).split(area);
+    fn row_widget(&self, row: &NoteRow, is_selected: bool) -> NoteRowWidget<'static> {
+        let selected_style = if is_selected {
+            Style::default().reversed()
+        } else {
+            Style::default()
+        };
 
-        let mut area_index = 0;
-        for (title, notes, is_daily) in &sections {
-            if notes.is_empty() {
-                continue;
+        match row {
+            NoteRow::Header(category) => {
+                let collapsed = self.state.collapsed.contains(category);
+                let count = category_notes(self.state, category)
+                    .iter()
+                    .filter(|path| matches_tag_filter(self.state, path))
+                    .count();
+                NoteRowWidget {
+                    title: format!("{} ({})", category_label(category), count),
+                    columns: Vec::new(),
+                    style: Style::default().dark_gray().bold().patch(selected_style),
+                    prefix: if collapsed { "▸ " } else { "▾ " },
+                }
             }
 
-            // Render section header
-            let header_area = section_areas[area_index];
-            area_index += 1;
-
-            let header_style = Style::default().dark_gray();
-            let display = format!(" ── {} ──", title);
-            let title_line = Line::from(display).style(header_style);
-            buf.set_line(header_area.x, header_area.y, &title_line, header_area.width);
-
-            // Render bordered note items
-            let list_area = section_areas[area_index];
-            area_index += 1;
+            NoteRow::Note(path) => {
+                let info = self.state.info.get(*path);
+                let is_daily = path
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .is_some_and(|n| n == "daily");
 
-            let list_block = Block::bordered().border_set(border::THICK);
-            let items_area = list_block.inner(list_area);
-            list_block.render(list_area, buf);
-
-            let mut y = items_area.y;
-            for (i, note_path) in notes.iter().enumerate() {
-                if y >= items_area.y + items_area.height {
-                    break;
+                let mut columns = Vec::new();
+                if let Some(info) = info
+                    && !info.tags.is_empty()
+                {
+                    columns.push((format_tags(&info.tags), Style::default().fg(Color::Cyan)));
+                }
+                if let Some(state) = info
+                    .and_then(|i| i.story_id)
+                    .and_then(|id| self.story_state(id))
+                {
+                    columns.push(state);
+                }
+                if let Some(modified) = info.and_then(|i| i.modified) {
+                    columns.push((
+                        format!("{:>3}", format_age(modified, self.now)),
+                        Style::default().dark_gray(),
+                    ));
                 }
 
-                let is_selected = self.state.selected_path.as_ref() == Some(note_path);
-                let is_last = i == notes.len() - 1;
-                let name = display_name(note_path, *is_daily);
-
-                let name_style = if is_selected {
-                    Style::default().bold()
-                } else {
-                    Style::default()
-                };
-                let name_line = Line::from(format!("  {}", name)).style(name_style);
-                buf.set_line(items_area.x, y, &name_line, items_area.width);
-                y += 1;
-
-                if !is_last && y < items_area.y + items_area.height {
-                    let divider_style = if is_selected {
-                        Style::default().yellow()
-                    } else {
-                        Style::default().dark_gray()
-                    };
-                    let divider = "─".repeat(items_area.width as usize);
-                    let divider_line = Line::from(divider).style(divider_style);
-                    buf.set_line(items_area.x, y, &divider_line, items_area.width);
-                    y += 1;
+                NoteRowWidget {
+                    title: display_name(path, info, is_daily),
+                    columns,
+                    style: selected_style,
+                    prefix: "    ",
                 }
             }
+        }
+    }
+}
+
+impl<'a> WidgetRef for NotesListView<'a> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let mut title = format!(" Notes · sorted by {} ", self.state.sort.label());
+        if !self.state.tag_filter.is_empty() {
+            title.push_str(&format!("· {} ", format_tags(&self.state.tag_filter)));
+        }
+        let mut block = Block::bordered().border_set(border::THICK).title(title);
+        if let Some(deleted) = &self.state.deleted {
+            let name = deleted
+                .original
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            block = block.title_bottom(
+                Line::from(format!(" Deleted {} · u to undo ", name))
+                    .style(Style::default().yellow()),
+            );
+        }
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let rows = visible_rows(self.state);
+        if rows.is_empty() {
+            let message = if self.state.tag_filter.is_empty() {
+                "No notes found.".to_string()
+            } else {
+                format!("No notes tagged {}.", format_tags(&self.state.tag_filter))
+            };
+            let paragraph = Paragraph::new(message)
+                .style(Style::default().gray())
+                .alignment(Alignment::Center);
 
-            // Skip spacing
-            area_index += 1;
+            if inner.height > 0 {
+                let centered_area = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                paragraph.render(centered_area, buf);
+            }
+            return;
         }
+
+        let builder = ListBuilder::new(|context| {
+            (
+                self.row_widget(&rows[context.index], context.is_selected),
+                1,
+            )
+        });
+
+        let list = ListView::new(builder, rows.len());
+        let mut list_state = ListState::default();
+        list_state.select(selected_row(self.state, &rows));
+
+        StatefulWidget::render(list, inner, buf, &mut list_state);
     }
 }
@@ -0,0 +1,107 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    app::{model::RepoPickerState, pane::repo_picker::matches},
+    view::description_modal::centered_rect,
+};
+
+/// Fuzzy finder over the repos in `repositories_directory`, used to pick where a
+/// worktree is created.
+pub struct RepoPicker<'a> {
+    state: &'a RepoPickerState,
+}
+
+impl<'a> RepoPicker<'a> {
+    pub fn new(state: &'a RepoPickerState) -> Self {
+        Self { state }
+    }
+
+    /// A repo with its matched chars highlighted.
+    fn repo_line(repo: &str, indices: &[usize], is_selected: bool) -> Line<'static> {
+        let base = if is_selected {
+            Style::default().yellow()
+        } else {
+            Style::default()
+        };
+
+        let mut spans = vec![Span::styled(if is_selected { "▶ " } else { "  " }, base)];
+        spans.extend(repo.chars().enumerate().map(|(idx, c)| {
+            let style = if indices.contains(&idx) {
+                base.add_modifier(Modifier::BOLD).cyan()
+            } else {
+                base
+            };
+            Span::styled(c.to_string(), style)
+        }));
+        Line::from(spans)
+    }
+}
+
+impl WidgetRef for RepoPicker<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(60, 60, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(format!(" Worktree for {} ", self.state.branch_name));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // input
+                Constraint::Length(1), // spacer
+                Constraint::Min(1),    // repos
+                Constraint::Length(1), // hint
+            ])
+            .split(inner);
+
+        let matches = matches(self.state);
+        let count = format!(" {}/{}", matches.len(), self.state.repos.len());
+        let input = Line::from(vec![
+            Span::raw(format!("> {}_", self.state.query)),
+            Span::styled(count, Style::default().dark_gray()),
+        ]);
+        buf.set_line(chunks[0].x, chunks[0].y, &input, chunks[0].width);
+
+        // keep the selection in view
+        let height = chunks[2].height as usize;
+        let offset = (self.state.selected + 1).saturating_sub(height);
+        let lines: Vec<Line> = if matches.is_empty() {
+            let empty = if self.state.refreshing {
+                "Looking for repos..."
+            } else {
+                "No matching repos"
+            };
+            vec![Line::from(empty).style(Style::default().dark_gray())]
+        } else {
+            matches
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(height)
+                .map(|(idx, (repo, indices))| {
+                    Self::repo_line(repo, indices, idx == self.state.selected)
+                })
+                .collect()
+        };
+        Paragraph::new(lines).render(chunks[2], buf);
+
+        let hint = if self.state.refreshing {
+            "Enter to create  ↑/↓ select  Esc to cancel  (refreshing repos)"
+        } else {
+            "Enter to create  ↑/↓ select  Esc to cancel"
+        };
+        Paragraph::new(hint)
+            .style(Style::default().dark_gray())
+            .render(chunks[3], buf);
+    }
+}
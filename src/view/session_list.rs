@@ -0,0 +1,154 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, StatefulWidget, Widget, WidgetRef},
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::story::Story,
+    app::{model::SessionListState, pane::session_list::session_story},
+    multiplexer::SessionInfo,
+    view::{description_modal::centered_rect, worktree_list::story_spans},
+};
+
+pub struct SessionListView<'a> {
+    state: &'a SessionListState,
+    stories: &'a [Story],
+}
+
+impl<'a> SessionListView<'a> {
+    pub fn new(state: &'a SessionListState, stories: &'a [Story]) -> Self {
+        Self { state, stories }
+    }
+}
+
+#[derive(Clone)]
+struct SessionRow {
+    line: Line<'static>,
+    is_selected: bool,
+}
+
+impl Widget for SessionRow {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let line = if self.is_selected {
+            self.line.style(Style::default().reversed())
+        } else {
+            self.line
+        };
+        buf.set_line(area.x, area.y, &line, area.width);
+    }
+}
+
+fn render_kill(sessions: &[SessionInfo], area: Rect, buf: &mut Buffer) {
+    let popup_area = centered_rect(60, 40, area);
+    Clear.render(popup_area, buf);
+
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(" Kill Sessions ");
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(format!("Kill {} session(s)?", sessions.len())).style(bold)];
+    lines.push(Line::from(""));
+    for session in sessions {
+        let mut spans = vec![Span::raw(format!("  {}", session.name))];
+        if session.attached {
+            spans.push(Span::styled(
+                "  attached",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("y to kill  n to cancel").style(Style::default().dark_gray()));
+
+    Paragraph::new(lines).block(block).render(popup_area, buf);
+}
+
+impl WidgetRef for SessionListView<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let title = if self.state.loading {
+            " Sessions (loading...) ".to_string()
+        } else {
+            format!(" Sessions ({}) ", self.state.items.len())
+        };
+        let block = Block::bordered().border_set(border::THICK).title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.state.items.is_empty() {
+            let text = if self.state.loading {
+                "Looking for sessions..."
+            } else {
+                "No story sessions. Open one with t on a story."
+            };
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().gray())
+                .alignment(Alignment::Center);
+
+            if inner.height > 0 {
+                let centered = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                paragraph.render(centered, buf);
+            }
+            return;
+        }
+
+        let name_width = self
+            .state
+            .items
+            .iter()
+            .map(|s| s.name.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let rows: Vec<SessionRow> = self
+            .state
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, session)| {
+                let story = session_story(session, self.stories);
+                let mut spans = vec![Span::styled(
+                    format!(" {:width$}  ", session.name, width = name_width),
+                    Style::default().fg(Color::Magenta),
+                )];
+                spans.extend(story_spans(story.map(|s| s.id).or(session.story_id), story));
+                if let Some(windows) = session.windows {
+                    spans.push(Span::styled(
+                        format!("  {} window(s)", windows),
+                        Style::default().dark_gray(),
+                    ));
+                }
+                if session.attached {
+                    spans.push(Span::styled(
+                        " ● attached",
+                        Style::default().fg(Color::Green),
+                    ));
+                }
+
+                SessionRow {
+                    line: Line::from(spans),
+                    is_selected: idx == self.state.selected,
+                }
+            })
+            .collect();
+
+        let row_count = rows.len();
+        let builder = ListBuilder::new(move |context| (rows[context.index].clone(), 1));
+
+        let list = ListView::new(builder, row_count);
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.state.selected));
+
+        StatefulWidget::render(list, inner, buf, &mut list_state);
+
+        if let Some(sessions) = &self.state.pending {
+            render_kill(sessions, area, buf);
+        }
+    }
+}
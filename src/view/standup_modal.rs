@@ -0,0 +1,50 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, BorderType, Paragraph, Widget, WidgetRef, Wrap},
+};
+
+use crate::{app::model::StandupModalState, view::description_modal::centered_rect};
+
+pub struct StandupModal<'a> {
+    state: &'a StandupModalState,
+}
+
+impl<'a> StandupModal<'a> {
+    pub fn new(state: &'a StandupModalState) -> Self {
+        Self { state }
+    }
+}
+
+impl WidgetRef for StandupModal<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(60, 60, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(" Standup ");
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),    // text
+                Constraint::Length(1), // hint
+            ])
+            .split(inner);
+
+        Paragraph::new(self.state.text.as_str())
+            .wrap(Wrap { trim: false })
+            .render(chunks[0], buf);
+
+        let hint = if self.state.copied {
+            Paragraph::new("Copied to clipboard  Esc to close").style(Style::default().green())
+        } else {
+            Paragraph::new("y to copy  Esc to close").style(Style::default().dark_gray())
+        };
+        hint.render(chunks[1], buf);
+    }
+}
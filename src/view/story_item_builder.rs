@@ -6,7 +6,7 @@ use ratatui::{
     widgets::Widget,
 };
 
-use crate::api::story::Story;
+use crate::{api::story::Story, view::worktree_list::status_badges, worktree::GitStatus};
 
 /// Renders a single story item with divider at the bottom
 pub struct StoryItemWidget<'a> {
@@ -16,6 +16,8 @@ pub struct StoryItemWidget<'a> {
     _width: u16,
     is_completed: bool,
     is_last: bool,
+    /// Local git state, when the story has a worktree or branch
+    git_status: Option<&'a GitStatus>,
 }
 
 impl<'a> StoryItemWidget<'a> {
@@ -26,6 +28,7 @@ impl<'a> StoryItemWidget<'a> {
         width: u16,
         is_completed: bool,
         is_last: bool,
+        git_status: Option<&'a GitStatus>,
     ) -> Self {
         Self {
             story,
@@ -34,6 +37,7 @@ impl<'a> StoryItemWidget<'a> {
             _width: width,
             is_completed,
             is_last,
+            git_status,
         }
     }
 
@@ -113,7 +117,43 @@ impl StoryItemWidget<'_> {
             base_style
         };
         spans.push(Span::styled(self.story.name.clone(), name_style));
+        spans.extend(self.pull_request_span());
+        // kept in color on completed stories, so leftover work stands out
+        if let Some(status) = self.git_status {
+            spans.extend(status_badges(status));
+        }
 
         Line::from(spans)
     }
+
+    /// Open pull requests, green when any is ready for review, or a check once all merged.
+    fn pull_request_span(&self) -> Option<Span<'static>> {
+        let prs = self.story.linked_pull_requests();
+        let open: Vec<_> = prs.iter().filter(|pr| pr.is_open()).collect();
+
+        let (text, color) = if !open.is_empty() {
+            let color = if open.iter().all(|pr| pr.draft) {
+                Color::DarkGray
+            } else {
+                Color::Green
+            };
+            let text = if open.len() > 1 {
+                format!(" PR×{}", open.len())
+            } else {
+                " PR".to_string()
+            };
+            (text, color)
+        } else if prs.iter().any(|pr| pr.merged) {
+            (" PR ✓".to_string(), Color::Magenta)
+        } else {
+            return None;
+        };
+
+        let color = if self.is_completed {
+            Color::DarkGray
+        } else {
+            color
+        };
+        Some(Span::styled(text, Style::default().fg(color)))
+    }
 }
@@ -182,6 +182,7 @@ impl<'a> WidgetRef for StoryListView<'a> {
 
             let section_stories: Vec<_> = section.stories.to_vec();
             let active_story = self.active_story;
+            let git_statuses = &self.state.git_statuses;
             let width = stories_area.width;
 
             let builder = ListBuilder::new(move |context| {
@@ -200,6 +201,7 @@ impl<'a> WidgetRef for StoryListView<'a> {
                     width,
                     is_completed,
                     is_last,
+                    git_statuses.get(&story.id),
                 );
                 let height = widget.height();
 
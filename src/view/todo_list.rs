@@ -0,0 +1,159 @@
+use chrono::NaiveDate;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, StatefulWidget, Widget, WidgetRef},
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    app::model::TodoListState,
+    note::todo::{Todo, TodoGroup},
+};
+
+pub struct TodoListView<'a> {
+    state: &'a TodoListState,
+    today: NaiveDate,
+}
+
+impl<'a> TodoListView<'a> {
+    pub fn new(state: &'a TodoListState) -> Self {
+        Self {
+            state,
+            today: crate::time::today(),
+        }
+    }
+}
+
+/// A row in the flattened todo list, either a note header or a checkbox.
+#[derive(Clone, Copy)]
+enum TodoRow<'a> {
+    Header(&'a TodoGroup),
+    Item {
+        todo: &'a Todo,
+        is_selected: bool,
+        today: NaiveDate,
+    },
+}
+
+impl Widget for TodoRow<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let line = match self {
+            TodoRow::Header(group) => {
+                let title = format!(" ── {} ({}) ──", group.title, group.open_count());
+                Line::from(title).style(Style::default().dark_gray())
+            }
+
+            TodoRow::Item {
+                todo,
+                is_selected,
+                today,
+            } => {
+                let checkbox = if todo.checked { "  [x] " } else { "  [ ] " };
+                let mut text_style = if todo.checked {
+                    Style::default().gray().crossed_out()
+                } else {
+                    Style::default()
+                };
+                if is_selected {
+                    text_style = text_style.bold();
+                }
+
+                let mut spans = vec![
+                    Span::styled(checkbox, Style::default().fg(Color::Blue)),
+                    Span::styled(todo.text.clone(), text_style),
+                ];
+
+                if let Some(due) = todo.due
+                    && !todo.checked
+                {
+                    let due_style = if due < today {
+                        Style::default().fg(Color::Red)
+                    } else if due == today {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().dark_gray()
+                    };
+                    spans.push(Span::styled(format!("  (due {})", due), due_style));
+                }
+
+                let line = Line::from(spans);
+                if is_selected {
+                    line.style(Style::default().reversed())
+                } else {
+                    line
+                }
+            }
+        };
+
+        buf.set_line(area.x, area.y, &line, area.width);
+    }
+}
+
+impl WidgetRef for TodoListView<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let title = if self.state.show_done {
+            " Todos (all) "
+        } else {
+            " Todos (open) "
+        };
+        let block = Block::bordered().border_set(border::THICK).title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let mut rows = Vec::new();
+        let mut selected_row = None;
+        for group in &self.state.groups {
+            let todos: Vec<_> = group
+                .todos
+                .iter()
+                .filter(|todo| self.state.show_done || !todo.checked)
+                .collect();
+
+            if todos.is_empty() {
+                continue;
+            }
+
+            rows.push(TodoRow::Header(group));
+            for todo in todos {
+                let is_selected = self
+                    .state
+                    .selected
+                    .as_ref()
+                    .is_some_and(|(path, line)| *path == group.path && *line == todo.line);
+                if is_selected {
+                    selected_row = Some(rows.len());
+                }
+                rows.push(TodoRow::Item {
+                    todo,
+                    is_selected,
+                    today: self.today,
+                });
+            }
+        }
+
+        if rows.is_empty() {
+            let paragraph = Paragraph::new("No open todos.")
+                .style(Style::default().gray())
+                .alignment(Alignment::Center);
+
+            if inner.height > 0 {
+                let centered = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                paragraph.render(centered, buf);
+            }
+            return;
+        }
+
+        let row_count = rows.len();
+        let builder = ListBuilder::new(move |context| (rows[context.index], 1));
+
+        let list = ListView::new(builder, row_count);
+        let mut list_state = ListState::default();
+        list_state.select(selected_row);
+
+        StatefulWidget::render(list, inner, buf, &mut list_state);
+    }
+}
@@ -0,0 +1,234 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, StatefulWidget, Widget, WidgetRef},
+};
+use tui_widget_list::{ListBuilder, ListState, ListView};
+
+use crate::{
+    api::story::Story,
+    app::{
+        model::{WorktreeListState, WorktreeRemoval},
+        pane::worktree_list::worktree_story,
+    },
+    view::description_modal::centered_rect,
+    worktree::GitStatus,
+};
+
+pub struct WorktreeListView<'a> {
+    state: &'a WorktreeListState,
+    stories: &'a [Story],
+}
+
+impl<'a> WorktreeListView<'a> {
+    pub fn new(state: &'a WorktreeListState, stories: &'a [Story]) -> Self {
+        Self { state, stories }
+    }
+}
+
+/// Badges for uncommitted changes, commits ahead/behind, unpushed commits and commits
+/// missing from the default branch. None for a clean, up to date checkout.
+pub fn status_badges(status: &GitStatus) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    if status.dirty {
+        spans.push(Span::styled(" ● dirty", Style::default().fg(Color::Yellow)));
+    }
+    if let Some((ahead, behind)) = status.ahead_behind {
+        if ahead > 0 {
+            spans.push(Span::styled(
+                format!(" ↑{}", ahead),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        if behind > 0 {
+            spans.push(Span::styled(
+                format!(" ↓{}", behind),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+    }
+    if status.unpushed > 0 {
+        spans.push(Span::styled(
+            format!(" {} unpushed", status.unpushed),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    if let Some(behind) = status.behind_default.filter(|&n| n > 0) {
+        spans.push(Span::styled(
+            format!(" {} behind default", behind),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    spans
+}
+
+/// Like `status_badges`, marking a clean checkout as such.
+pub fn status_spans(status: &GitStatus) -> Vec<Span<'static>> {
+    let mut spans = status_badges(status);
+    if spans.is_empty() {
+        spans.push(Span::styled(" clean", Style::default().dark_gray()));
+    }
+    spans
+}
+
+/// The story with its state, or the bare ID when the story isn't loaded.
+pub fn story_spans(story_id: Option<i32>, story: Option<&Story>) -> Vec<Span<'static>> {
+    match (story, story_id) {
+        (Some(story), _) => {
+            let (state, color) = if story.completed {
+                ("done", Color::Green)
+            } else if story.blocked {
+                ("blocked", Color::Red)
+            } else if story.started {
+                ("started", Color::Blue)
+            } else {
+                ("unstarted", Color::Gray)
+            };
+            vec![
+                Span::raw(format!("sc-{} {} ", story.id, story.name)),
+                Span::styled(format!("[{}]", state), Style::default().fg(color)),
+            ]
+        }
+        (None, Some(id)) => vec![Span::styled(
+            format!("sc-{} (not in loaded stories)", id),
+            Style::default().dark_gray(),
+        )],
+        (None, None) => vec![Span::styled("no story", Style::default().dark_gray())],
+    }
+}
+
+#[derive(Clone)]
+struct WorktreeRow {
+    line: Line<'static>,
+    is_selected: bool,
+}
+
+impl Widget for WorktreeRow {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let line = if self.is_selected {
+            self.line.style(Style::default().reversed())
+        } else {
+            self.line
+        };
+        buf.set_line(area.x, area.y, &line, area.width);
+    }
+}
+
+fn render_removal(removal: &WorktreeRemoval, area: Rect, buf: &mut Buffer) {
+    let popup_area = centered_rect(60, 40, area);
+    Clear.render(popup_area, buf);
+
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(" Remove Worktrees ");
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines =
+        vec![Line::from(format!("Remove {} worktree(s)?", removal.worktrees.len())).style(bold)];
+    lines.push(Line::from(""));
+    for worktree in &removal.worktrees {
+        let mut spans = vec![Span::raw(format!(
+            "  {} {}",
+            worktree.repo,
+            worktree.branch.as_deref().unwrap_or("(detached)")
+        ))];
+        if worktree.status.has_unsaved_work() {
+            spans.push(Span::styled(
+                "  kept: unsaved work",
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    let checkbox = if removal.delete_branches {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    lines.push(Line::from(format!("{} also delete branches (b)", checkbox)));
+    lines.push(Line::from("y to remove  n to cancel").style(Style::default().dark_gray()));
+
+    Paragraph::new(lines).block(block).render(popup_area, buf);
+}
+
+impl WidgetRef for WorktreeListView<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let title = if self.state.loading {
+            " Worktrees (loading...) ".to_string()
+        } else {
+            format!(" Worktrees ({}) ", self.state.items.len())
+        };
+        let block = Block::bordered().border_set(border::THICK).title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.state.items.is_empty() {
+            let text = if self.state.loading {
+                "Looking for worktrees..."
+            } else {
+                "No worktrees. Create one from a story's action menu."
+            };
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().gray())
+                .alignment(Alignment::Center);
+
+            if inner.height > 0 {
+                let centered = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                paragraph.render(centered, buf);
+            }
+            return;
+        }
+
+        let repo_width = self
+            .state
+            .items
+            .iter()
+            .map(|wt| wt.repo.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let rows: Vec<WorktreeRow> = self
+            .state
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, worktree)| {
+                let story = worktree_story(worktree, self.stories);
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" {:width$}  ", worktree.repo, width = repo_width),
+                        Style::default().fg(Color::Magenta),
+                    ),
+                    Span::raw(format!(
+                        "{}  ",
+                        worktree.branch.as_deref().unwrap_or("(detached)")
+                    )),
+                ];
+                spans.extend(story_spans(worktree.story_id, story));
+                spans.extend(status_spans(&worktree.status));
+
+                WorktreeRow {
+                    line: Line::from(spans),
+                    is_selected: idx == self.state.selected,
+                }
+            })
+            .collect();
+
+        let row_count = rows.len();
+        let builder = ListBuilder::new(move |context| (rows[context.index].clone(), 1));
+
+        let list = ListView::new(builder, row_count);
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.state.selected));
+
+        StatefulWidget::render(list, inner, buf, &mut list_state);
+
+        if let Some(removal) = &self.state.pending {
+            render_removal(removal, area, buf);
+        }
+    }
+}
@@ -1,97 +1,126 @@
 use std::{
+    collections::HashMap,
+    fs,
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command as StdCommand, Stdio},
+    time::Duration,
 };
 
 use anyhow::Context;
+use serde::{Deserialize, Serialize};
 use slugify::slugify;
 use tokio::process::Command as TokioCommand;
 use which::which;
 
-use crate::{config::Config, dbg_file};
+use crate::{
+    api::story::Story,
+    branch_name::{branch_name, story_id_from_branch},
+    cache::Cache,
+    config::Config,
+    dbg_file,
+};
 
-pub fn check_worktree_dependencies() -> anyhow::Result<()> {
-    which("fd").context("Please make sure 'fd' is installed and in $PATH")?;
-    which("fzf").context("Please make sure 'fzf' is installed and in $PATH")?;
-    Ok(())
-}
+const REPO_CACHE_FILE: &str = "repos.json";
 
-/// Finds repos that have a .git directory (regular repos).
-async fn find_standard_repos(repos_dir: &str) -> anyhow::Result<Vec<String>> {
-    let output = TokioCommand::new("fd")
-        .args(["-t", "d", "-I", "-H", "^\\.git$", repos_dir, "-x", "dirname", "{}"])
-        .output()
-        .await?;
+/// Directories that are never searched for repos. Worktrees are excluded anyway and
+/// `node_modules` is large enough to slow discovery down noticeably.
+const SKIPPED_DIRS: [&str; 3] = [".git", ".worktrees", "node_modules"];
 
-    if !output.status.success() {
-        anyhow::bail!("fd failed: {}", String::from_utf8_lossy(&output.stderr));
-    }
+/// How often the git state shown on stories is checked again.
+pub const STORY_STATUS_REFRESH: Duration = Duration::from_secs(60);
 
-    let prefix = format!("{}/", repos_dir);
-    Ok(String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|l| l.replace(&prefix, ""))
-        .filter(|l| !l.is_empty())
-        .collect())
+/// Repos found by the last discovery, so the picker has something to show straight away.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RepoCache {
+    repositories_directory: PathBuf,
+    repos: Vec<String>,
 }
 
-/// Finds repos that have a .git file pointing to a modules dir (submodules).
-/// Excludes worktree .git files, which point to a worktrees dir instead.
-async fn find_submodule_repos(repos_dir: &str) -> anyhow::Result<Vec<String>> {
-    let output = TokioCommand::new("fd")
-        .args(["-t", "f", "-I", "-H", "^\\.git$", repos_dir])
-        .output()
-        .await?;
+fn repo_cache_file(cache_dir: &Path) -> PathBuf {
+    cache_dir.join(REPO_CACHE_FILE)
+}
 
-    if !output.status.success() {
-        anyhow::bail!("fd failed: {}", String::from_utf8_lossy(&output.stderr));
+/// Repos cached for `config.repositories_directory`, empty if there are none yet or they
+/// were found under a different directory.
+pub fn cached_repos(config: &Config) -> Vec<String> {
+    fs::read_to_string(repo_cache_file(&config.cache_dir))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<RepoCache>(&contents).ok())
+        .filter(|cache| cache.repositories_directory == config.repositories_directory)
+        .map(|cache| cache.repos)
+        .unwrap_or_default()
+}
+
+fn save_repo_cache(config: &Config, repos: &[String]) -> anyhow::Result<()> {
+    let cache = RepoCache {
+        repositories_directory: config.repositories_directory.clone(),
+        repos: repos.to_vec(),
+    };
+    fs::create_dir_all(&config.cache_dir)?;
+    fs::write(repo_cache_file(&config.cache_dir), serde_json::to_string(&cache)?)?;
+    Ok(())
+}
+
+/// Whether `dir` is a regular repo (a .git directory) or a submodule (a .git file pointing
+/// to a modules dir). Worktree .git files point to a worktrees dir instead.
+fn is_repo(dir: &Path) -> bool {
+    let git = dir.join(".git");
+    if git.is_dir() {
+        return true;
     }
+    fs::read_to_string(&git).is_ok_and(|contents| !contents.contains("worktrees"))
+}
 
-    let prefix = format!("{}/", repos_dir);
-    let git_files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
+/// Finds regular repos and submodules under `repos_dir`, as sorted paths relative to it.
+pub fn discover_repos(repos_dir: &Path) -> Vec<String> {
+    let mut repos = Vec::new();
+    let mut stack = vec![repos_dir.to_path_buf()];
 
-    let contents: Vec<Option<(String, String)>> = futures::future::join_all(
-        git_files.into_iter().map(|git_file| async move {
-            let content = tokio::fs::read_to_string(&git_file).await.ok()?;
-            Some((git_file, content))
-        })
-    ).await;
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
 
-    Ok(contents
-        .into_iter()
-        .flatten()
-        .filter(|(_, content)| !content.contains("worktrees"))
-        .map(|(git_file, _)| {
-            Path::new(&git_file)
-                .parent()
-                .and_then(|p| p.to_str())
-                .unwrap_or("")
-                .replace(&prefix, "")
-        })
-        .filter(|p| !p.is_empty())
-        .collect())
-}
+        for entry in entries.flatten() {
+            // file_type doesn't follow symlinks, so linked directories aren't walked twice
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            let skipped = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+            if !is_dir || skipped {
+                continue;
+            }
 
-/// Returns a string with newline seperated repo names
-pub async fn get_repo_list(config: &Config) -> anyhow::Result<String> {
-    let repos_dir = config.repositories_directory.to_str().unwrap();
+            let path = entry.path();
+            if is_repo(&path)
+                && let Ok(relative) = path.strip_prefix(repos_dir)
+            {
+                repos.push(relative.display().to_string());
+            }
+            stack.push(path);
+        }
+    }
 
-    let (mut standard, submodules) = tokio::try_join!(
-        find_standard_repos(repos_dir),
-        find_submodule_repos(repos_dir),
-    )?;
+    repos.sort();
+    repos.dedup();
+    repos
+}
 
-    standard.extend(submodules);
-    standard.sort();
-    standard.dedup();
-    Ok(standard.join("\n"))
+/// Discovers the repos under `config.repositories_directory` and caches them.
+pub fn refresh_repo_cache(config: &Config) -> Vec<String> {
+    let repos = discover_repos(&config.repositories_directory);
+    if let Err(e) = save_repo_cache(config, &repos) {
+        dbg_file!("Failed to save repo cache: {}", e);
+    }
+    repos
 }
 
-pub fn select_repo_with_fzf(repo_list: &str) -> anyhow::Result<String> {
+/// Picks one of `repos` with an external fzf, used when `use_fzf` is set.
+pub fn select_repo_with_fzf(repos: &[String]) -> anyhow::Result<String> {
+    which("fzf").context("Please make sure 'fzf' is installed and in $PATH")?;
+
     let mut fzf = StdCommand::new("fzf")
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -101,7 +130,7 @@ pub fn select_repo_with_fzf(repo_list: &str) -> anyhow::Result<String> {
     fzf.stdin
         .take()
         .context("failed to open stdin")?
-        .write_all(repo_list.as_bytes())?;
+        .write_all(repos.join("\n").as_bytes())?;
 
     let output = fzf.wait_with_output()?;
 
@@ -115,9 +144,50 @@ pub fn select_repo_with_fzf(repo_list: &str) -> anyhow::Result<String> {
         .to_string())
 }
 
-pub async fn create_worktree(repo_path: &Path, branch_name: &str) -> anyhow::Result<()> {
-    let slug = slugify!(branch_name);
-    let worktree_path = format!(".worktrees/{}", slug);
+/// Where the worktree for `branch_name` lives inside `repo_path`.
+pub fn worktree_path(repo_path: &Path, branch_name: &str) -> PathBuf {
+    repo_path.join(".worktrees").join(slugify!(branch_name))
+}
+
+/// The repo remembered for a story and where its worktree is, or would be, created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoryRepo {
+    pub repo: String,
+    pub repo_path: PathBuf,
+    pub worktree_path: PathBuf,
+}
+
+impl StoryRepo {
+    /// The worktree once it has been created, otherwise the repo itself.
+    pub fn work_dir(&self) -> &Path {
+        if self.worktree_path.is_dir() {
+            &self.worktree_path
+        } else {
+            &self.repo_path
+        }
+    }
+}
+
+pub fn story_repo(config: &Config, cache: &Cache, story: &Story) -> Option<StoryRepo> {
+    let repo = cache.story_repo(story)?.to_string();
+    let repo_path = config.repositories_directory.join(&repo);
+    let branch = branch_name(&config.branch_template, story, cache.mention_name.as_deref());
+    Some(StoryRepo {
+        worktree_path: worktree_path(&repo_path, &branch),
+        repo,
+        repo_path,
+    })
+}
+
+/// Checks out `branch_name`, creating it off HEAD when it doesn't exist yet. A
+/// `linked_branch` was pushed by someone, so it is only ever tracked from origin.
+pub async fn create_worktree(
+    repo_path: &Path,
+    branch_name: &str,
+    linked_branch: bool,
+) -> anyhow::Result<()> {
+    let worktree_path = worktree_path(repo_path, branch_name);
+    let worktree_path = worktree_path.to_string_lossy();
 
     dbg_file!("repo path {}", repo_path.display());
     TokioCommand::new("mkdir")
@@ -126,12 +196,25 @@ pub async fn create_worktree(repo_path: &Path, branch_name: &str) -> anyhow::Res
         .status()
         .await?;
 
-    if !branch_exists(repo_path, branch_name).await {
-        create_branch(repo_path, branch_name).await?;
+    if !ref_exists(repo_path, &format!("refs/heads/{}", branch_name)).await {
+        // a branch someone else pushed, e.g. the head of a linked pull request, is tracked
+        let remote_ref = format!("refs/remotes/origin/{}", branch_name);
+        fetch_branch(repo_path, branch_name).await;
+        if ref_exists(repo_path, &remote_ref).await {
+            create_branch(repo_path, branch_name, Some(&remote_ref)).await?;
+        } else if linked_branch {
+            anyhow::bail!(
+                "{} couldn't be fetched from origin of the repo at {}",
+                branch_name,
+                repo_path.display()
+            );
+        } else {
+            create_branch(repo_path, branch_name, None).await?;
+        }
     }
 
     let output = TokioCommand::new("git")
-        .args(["worktree", "add", &worktree_path, branch_name])
+        .args(["worktree", "add", &*worktree_path, branch_name])
         .current_dir(repo_path)
         .output()
         .await
@@ -152,13 +235,9 @@ pub async fn create_worktree(repo_path: &Path, branch_name: &str) -> anyhow::Res
     Ok(())
 }
 
-async fn branch_exists(repo_path: &Path, branch_name: &str) -> bool {
+async fn ref_exists(repo_path: &Path, git_ref: &str) -> bool {
     let result = TokioCommand::new("git")
-        .args([
-            "rev-parse",
-            "--verify",
-            &format!("refs/heads/{}", branch_name),
-        ])
+        .args(["rev-parse", "--verify", git_ref])
         .current_dir(repo_path)
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -168,12 +247,30 @@ async fn branch_exists(repo_path: &Path, branch_name: &str) -> bool {
     result.map(|s| s.success()).unwrap_or(false)
 }
 
-async fn create_branch(repo_path: &Path, branch_name: &str) -> anyhow::Result<()> {
-    let status = TokioCommand::new("git")
-        .args(["branch", branch_name])
+/// Best effort, whether the remote branch exists is checked afterwards.
+async fn fetch_branch(repo_path: &Path, branch_name: &str) {
+    let output = TokioCommand::new("git")
+        .args(["fetch", "origin", branch_name])
         .current_dir(repo_path)
-        .status()
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
         .await;
+    dbg_file!("git fetch origin {}: {:?}", branch_name, output.map(|o| o.status));
+}
+
+/// Branches off HEAD, or tracks `upstream` when given.
+async fn create_branch(
+    repo_path: &Path,
+    branch_name: &str,
+    upstream: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut command = TokioCommand::new("git");
+    match upstream {
+        Some(upstream) => command.args(["branch", "--track", branch_name, upstream]),
+        None => command.args(["branch", branch_name]),
+    };
+    let status = command.current_dir(repo_path).status().await;
 
     let success = status.map(|s| s.success()).unwrap_or(false);
 
@@ -187,3 +284,364 @@ async fn create_branch(repo_path: &Path, branch_name: &str) -> anyhow::Result<()
         )
     }
 }
+
+/// Working tree state of a worktree, from `git status`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Uncommitted or untracked changes
+    pub dirty: bool,
+    /// Commits ahead of and behind the upstream branch, when there is one
+    pub ahead_behind: Option<(usize, usize)>,
+    /// Commits that aren't on any remote branch
+    pub unpushed: usize,
+    /// Commits on the default branch that aren't on this one, when it is known
+    pub behind_default: Option<usize>,
+}
+
+impl GitStatus {
+    /// Whether removing the worktree and its branch could lose work.
+    pub fn has_unsaved_work(&self) -> bool {
+        self.dirty || self.unpushed > 0
+    }
+
+    /// Combines the state of several checkouts of the same story.
+    fn merge(&mut self, other: GitStatus) {
+        self.dirty |= other.dirty;
+        self.ahead_behind = self.ahead_behind.or(other.ahead_behind);
+        self.unpushed += other.unpushed;
+        self.behind_default = self.behind_default.max(other.behind_default);
+    }
+}
+
+/// A linked worktree of one of the repos in `repositories_directory`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    /// Repo relative to `repositories_directory`
+    pub repo: String,
+    pub path: PathBuf,
+    /// `None` for a detached HEAD
+    pub branch: Option<String>,
+    pub story_id: Option<i32>,
+    pub status: GitStatus,
+}
+
+/// Parses `git worktree list --porcelain` into `(path, branch)` pairs, leaving out the
+/// main worktree, which is always listed first.
+pub fn parse_worktree_list(output: &str) -> Vec<(PathBuf, Option<String>)> {
+    output
+        .split("\n\n")
+        .skip(1)
+        .filter_map(|entry| {
+            let path = entry.lines().find_map(|l| l.strip_prefix("worktree "))?;
+            let branch = entry
+                .lines()
+                .find_map(|l| l.strip_prefix("branch "))
+                .map(|b| b.strip_prefix("refs/heads/").unwrap_or(b).to_string());
+            Some((PathBuf::from(path), branch))
+        })
+        .collect()
+}
+
+/// Parses `git status --porcelain=v2 --branch`.
+pub fn parse_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in output.lines() {
+        if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            let mut counts = ab
+                .split_whitespace()
+                .map(|n| n.trim_start_matches(['+', '-']).parse().unwrap_or(0));
+            status.ahead_behind = Some((counts.next().unwrap_or(0), counts.next().unwrap_or(0)));
+        } else if !line.starts_with('#') && !line.is_empty() {
+            status.dirty = true;
+        }
+    }
+    status
+}
+
+async fn git_output(dir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = TokioCommand::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .with_context(|| format!("failed to run git {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub async fn worktree_status(path: &Path) -> anyhow::Result<GitStatus> {
+    let output = git_output(path, &["status", "--porcelain=v2", "--branch"]).await?;
+    let mut status = parse_status(&output);
+    status.unpushed = git_output(path, &["rev-list", "--count", "HEAD", "--not", "--remotes"])
+        .await?
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    Ok(status)
+}
+
+/// The branch story branches are compared against: what `origin/HEAD` points to, or a
+/// local `main` or `master`.
+async fn default_branch(repo_path: &Path) -> Option<String> {
+    let args = ["symbolic-ref", "--short", "refs/remotes/origin/HEAD"];
+    if let Ok(head) = git_output(repo_path, &args).await {
+        return Some(head.trim().to_string());
+    }
+    for branch in ["main", "master"] {
+        if ref_exists(repo_path, &format!("refs/heads/{}", branch)).await {
+            return Some(branch.to_string());
+        }
+    }
+    None
+}
+
+async fn commit_count(dir: &Path, revs: &[&str]) -> Option<usize> {
+    let args = [&["rev-list", "--count"], revs].concat();
+    git_output(dir, &args).await.ok()?.trim().parse().ok()
+}
+
+async fn commits_behind(dir: &Path, rev: &str, default: Option<&str>) -> Option<usize> {
+    commit_count(dir, &[&format!("{}..{}", rev, default?)]).await
+}
+
+async fn repo_worktrees(repos_dir: &Path, repo: String) -> Vec<WorktreeInfo> {
+    let repo_path = repos_dir.join(&repo);
+    let Ok(list) = git_output(&repo_path, &["worktree", "list", "--porcelain"]).await else {
+        return Vec::new();
+    };
+    let default = default_branch(&repo_path).await;
+
+    let mut worktrees = Vec::new();
+    for (path, branch) in parse_worktree_list(&list) {
+        // prunable worktrees whose directory was deleted can't be inspected
+        let Ok(mut status) = worktree_status(&path).await else {
+            continue;
+        };
+        status.behind_default = commits_behind(&path, "HEAD", default.as_deref()).await;
+        worktrees.push(WorktreeInfo {
+            repo: repo.clone(),
+            story_id: branch.as_deref().and_then(story_id_from_branch),
+            path,
+            branch,
+            status,
+        });
+    }
+    worktrees
+}
+
+/// Every linked worktree of the repos under `repositories_directory`.
+pub async fn list_worktrees(config: &Config) -> Vec<WorktreeInfo> {
+    let config_clone = config.clone();
+    let repos = tokio::task::spawn_blocking(move || refresh_repo_cache(&config_clone))
+        .await
+        .unwrap_or_default();
+
+    let repos_dir = &config.repositories_directory;
+    futures::future::join_all(repos.into_iter().map(|repo| repo_worktrees(repos_dir, repo)))
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Story branches of a repo that aren't checked out in a linked worktree. The one checked
+/// out in the repo itself gets the repo's working tree state.
+async fn repo_branch_statuses(repo_path: &Path, skipped: &[String]) -> Vec<(i32, GitStatus)> {
+    let args = ["for-each-ref", "--format=%(refname:short)", "refs/heads"];
+    let Ok(branches) = git_output(repo_path, &args).await else {
+        return Vec::new();
+    };
+    let current = git_output(repo_path, &["branch", "--show-current"])
+        .await
+        .unwrap_or_default();
+    let default = default_branch(repo_path).await;
+
+    let mut statuses = Vec::new();
+    for branch in branches.lines().filter(|b| !skipped.iter().any(|s| s == b)) {
+        let Some(story_id) = story_id_from_branch(branch) else {
+            continue;
+        };
+        let rev = format!("refs/heads/{}", branch);
+        let mut status = if branch == current.trim() {
+            worktree_status(repo_path).await.unwrap_or_default()
+        } else {
+            GitStatus {
+                unpushed: commit_count(repo_path, &[&rev, "--not", "--remotes"])
+                    .await
+                    .unwrap_or(0),
+                ..GitStatus::default()
+            }
+        };
+        status.behind_default = commits_behind(repo_path, &rev, default.as_deref()).await;
+        statuses.push((story_id, status));
+    }
+    statuses
+}
+
+async fn repo_story_statuses(repos_dir: &Path, repo: String) -> Vec<(i32, GitStatus)> {
+    let repo_path = repos_dir.join(&repo);
+    let worktrees = repo_worktrees(repos_dir, repo).await;
+    let checked_out: Vec<String> = worktrees.iter().filter_map(|w| w.branch.clone()).collect();
+
+    let mut statuses: Vec<(i32, GitStatus)> = worktrees
+        .into_iter()
+        .filter_map(|w| Some((w.story_id?, w.status)))
+        .collect();
+    statuses.extend(repo_branch_statuses(&repo_path, &checked_out).await);
+    statuses
+}
+
+/// One status per story, combining the story's checkouts across repos.
+pub fn merge_story_statuses(statuses: Vec<(i32, GitStatus)>) -> HashMap<i32, GitStatus> {
+    let mut merged: HashMap<i32, GitStatus> = HashMap::new();
+    for (story_id, status) in statuses {
+        merged.entry(story_id).or_default().merge(status);
+    }
+    merged
+}
+
+/// Local git state of the stories with a worktree or a branch in one of the repos under
+/// `repositories_directory`, by story ID.
+pub async fn story_git_statuses(config: &Config) -> HashMap<i32, GitStatus> {
+    let mut repos = cached_repos(config);
+    if repos.is_empty() {
+        let config_clone = config.clone();
+        repos = tokio::task::spawn_blocking(move || refresh_repo_cache(&config_clone))
+            .await
+            .unwrap_or_default();
+    }
+
+    let repos_dir = &config.repositories_directory;
+    let statuses =
+        futures::future::join_all(repos.into_iter().map(|repo| repo_story_statuses(repos_dir, repo)))
+            .await;
+    merge_story_statuses(statuses.into_iter().flatten().collect())
+}
+
+/// Removes a worktree, and its branch when `delete_branch` is set. Refuses when the
+/// worktree has uncommitted changes or commits that were never pushed.
+pub async fn remove_worktree(
+    config: &Config,
+    worktree: &WorktreeInfo,
+    delete_branch: bool,
+) -> anyhow::Result<()> {
+    let status = worktree_status(&worktree.path).await?;
+    if status.dirty {
+        anyhow::bail!("{} has uncommitted changes", worktree.path.display());
+    }
+    if status.unpushed > 0 {
+        anyhow::bail!(
+            "{} has {} unpushed commits",
+            worktree.path.display(),
+            status.unpushed
+        );
+    }
+
+    let repo_path = config.repositories_directory.join(&worktree.repo);
+    let path = worktree.path.to_string_lossy();
+    git_output(&repo_path, &["worktree", "remove", &path]).await?;
+
+    if delete_branch && let Some(branch) = &worktree.branch {
+        // every commit is on a remote, so nothing is lost even if it isn't merged
+        git_output(&repo_path, &["branch", "-D", branch]).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_repos_and_submodules_but_not_worktrees() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        fs::create_dir_all(root.join("work/api/.git")).unwrap();
+        fs::create_dir_all(root.join("work/api/vendor/lib")).unwrap();
+        fs::write(
+            root.join("work/api/vendor/lib/.git"),
+            "gitdir: ../../.git/modules/lib\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("work/api/.worktrees/feat")).unwrap();
+        fs::write(
+            root.join("work/api/.worktrees/feat/.git"),
+            "gitdir: ../../.git/worktrees/feat\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("scratch/not-a-repo")).unwrap();
+        fs::create_dir_all(root.join("web/.git")).unwrap();
+
+        assert_eq!(
+            discover_repos(root),
+            vec!["web", "work/api", "work/api/vendor/lib"]
+        );
+    }
+
+    #[test]
+    fn parses_worktree_list_and_status() {
+        let list = "worktree /r/api\nHEAD abc\nbranch refs/heads/main\n\n\
+                    worktree /r/api/.worktrees/jane-sc-12-fix\nHEAD def\n\
+                    branch refs/heads/jane/sc-12/fix\n\n\
+                    worktree /r/api/.worktrees/detached\nHEAD 123\ndetached\n";
+        assert_eq!(
+            parse_worktree_list(list),
+            vec![
+                (
+                    PathBuf::from("/r/api/.worktrees/jane-sc-12-fix"),
+                    Some("jane/sc-12/fix".to_string())
+                ),
+                (PathBuf::from("/r/api/.worktrees/detached"), None),
+            ]
+        );
+
+        let status = parse_status(
+            "# branch.oid abc\n# branch.head fix\n# branch.upstream origin/fix\n\
+             # branch.ab +2 -1\n? notes.txt\n",
+        );
+        assert_eq!(status.ahead_behind, Some((2, 1)));
+        assert!(status.dirty);
+        assert_eq!(parse_status("# branch.head fix\n"), GitStatus::default());
+    }
+
+    #[test]
+    fn merges_checkouts_of_a_story() {
+        let worktree = GitStatus {
+            dirty: true,
+            unpushed: 1,
+            behind_default: Some(2),
+            ..GitStatus::default()
+        };
+        let branch = GitStatus {
+            unpushed: 3,
+            behind_default: Some(5),
+            ..GitStatus::default()
+        };
+
+        let merged = merge_story_statuses(vec![
+            (12, worktree),
+            (13, GitStatus::default()),
+            (12, branch),
+        ]);
+        assert_eq!(
+            merged[&12],
+            GitStatus {
+                dirty: true,
+                ahead_behind: None,
+                unpushed: 4,
+                behind_default: Some(5),
+            }
+        );
+        assert_eq!(merged[&13], GitStatus::default());
+    }
+}